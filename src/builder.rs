@@ -0,0 +1,202 @@
+use std::convert::TryFrom;
+
+use crate::{
+    interval::{BeginSorted, EndSorted, Interval},
+    interval_tree::IntervalTree,
+};
+
+/// Builder of an [`IntervalTree`](struct.IntervalTree.html) whose range is not known in advance.
+///
+/// The intervals are only collected until [`build`](#method.build), which creates the tree on the
+/// smallest range containing all of them and inserts them at once.
+///
+/// # Examples
+///
+/// ```rust
+/// use interval_tree::{Builder, Interval};
+///
+/// let mut builder = Builder::new();
+/// builder.push(40..60);
+/// builder.extend(vec![5..10, 85..95]);
+///
+/// let tree = builder.build().unwrap();
+/// assert_eq!(tree.find_with_point(50), [&(40..60)].iter().cloned().collect());
+/// assert_eq!(tree.find_with_point(90), [&(85..95)].iter().cloned().collect());
+/// ```
+#[derive(Clone, Debug)]
+pub struct Builder<T> {
+    intervals: Vec<T>,
+}
+
+impl<T> Builder<T>
+where
+    T: Interval,
+    <T as Iterator>::Item: PartialOrd,
+    BeginSorted<T>: Ord,
+    EndSorted<T>: Ord,
+{
+    /// Creates a builder with no intervals.
+    pub fn new() -> Self {
+        Self {
+            intervals: Vec::new(),
+        }
+    }
+
+    /// Adds an [`Interval`](trait.Interval.html) to the tree to build.
+    pub fn push(&mut self, interval: T) {
+        self.intervals.push(interval);
+    }
+
+    /// Builds the interval tree on the smallest range containing all of the intervals, or returns
+    /// `None` if no interval has been added.
+    ///
+    /// An empty interval, such as `5..5`, is contained in a range only if its point is, so the
+    /// range is extended one past an empty interval at its end. Points out of that range cannot
+    /// be queried on the built tree.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an empty interval lies at the largest value of the point type, which no range
+    /// contains.
+    pub fn build(mut self) -> Option<IntervalTree<T>>
+    where
+        T::Item: CheckedAdd + TryFrom<u8>,
+    {
+        let min = |a: T::Item, b: T::Item| if b < a { b } else { a };
+        let max = |a: T::Item, b: T::Item| if b > a { b } else { a };
+
+        let (begin, end) = self
+            .intervals
+            .iter()
+            .fold(None, |bounds, intv| match bounds {
+                Some((begin, end)) => Some((min(begin, intv.begin()), max(end, intv.end()))),
+                None => Some((intv.begin(), intv.end())),
+            })?;
+        let end = if self.intervals.iter().any(|intv| intv.begin() >= end) {
+            end.checked_add(&T::Item::try_from(1).ok().unwrap())
+                .expect("an empty interval at the largest point is not contained in any range")
+        } else {
+            end
+        };
+
+        self.intervals
+            .sort_by(|a, b| a.begin().partial_cmp(&b.begin()).unwrap());
+
+        let mut tree = IntervalTree::new(T::from_bounds(begin, end));
+        tree.insert_many_sorted(self.intervals);
+        Some(tree)
+    }
+}
+
+/// Addition checking for overflow, required by [`Builder::build`](struct.Builder.html#method.build)
+/// of the points to extend a range by one.
+///
+/// Implemented for the primitive integers.
+pub trait CheckedAdd: Sized {
+    /// Adds `rhs` to `self`, or returns `None` on overflow.
+    fn checked_add(&self, rhs: &Self) -> Option<Self>;
+}
+
+macro_rules! impl_checked_add {
+    ($int:ty) => {
+        impl CheckedAdd for $int {
+            fn checked_add(&self, rhs: &Self) -> Option<Self> {
+                <$int>::checked_add(*self, *rhs)
+            }
+        }
+    };
+}
+
+impl_checked_add!(u8);
+impl_checked_add!(i8);
+impl_checked_add!(u16);
+impl_checked_add!(i16);
+impl_checked_add!(u32);
+impl_checked_add!(i32);
+impl_checked_add!(u64);
+impl_checked_add!(i64);
+impl_checked_add!(u128);
+impl_checked_add!(i128);
+impl_checked_add!(usize);
+impl_checked_add!(isize);
+
+impl<T> Default for Builder<T>
+where
+    T: Interval,
+    <T as Iterator>::Item: PartialOrd,
+    BeginSorted<T>: Ord,
+    EndSorted<T>: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Extend<T> for Builder<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.intervals.extend(iter);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interval_tree::tests::sample_intervals;
+
+    #[test]
+    fn build_matches_inserted_tree() {
+        let intervals: Vec<_> = sample_intervals().into_iter().rev().collect();
+
+        let mut builder = Builder::new();
+        builder.push(5..6);
+        builder.extend(intervals.iter().cloned());
+        let tree = builder.build().unwrap();
+
+        let mut naive = IntervalTree::new(0..100);
+        naive.insert(5..6);
+        for intv in intervals {
+            naive.insert(intv);
+        }
+
+        assert_eq!(tree.check_invariants(), Ok(()));
+        for p in 5..95 {
+            assert_eq!(tree.find_with_point(p), naive.find_with_point(p));
+        }
+        assert_eq!(
+            tree.find_with_interval(40..60),
+            naive.find_with_interval(40..60)
+        );
+    }
+
+    #[test]
+    fn build_empty() {
+        assert!(Builder::<std::ops::Range<i32>>::new().build().is_none());
+    }
+
+    #[test]
+    fn build_empty_intervals() {
+        let mut builder = Builder::new();
+        builder.extend(vec![0..10, 10..10]);
+        let tree = builder.build().unwrap();
+        assert_eq!(tree.check_invariants(), Ok(()));
+        assert_eq!(tree.count_of(&(10..10)), 1);
+        assert_eq!(
+            tree.find_with_point(9),
+            [&(0..10)].iter().cloned().collect()
+        );
+
+        let mut builder = Builder::new();
+        builder.push(5u8..5);
+        let tree = builder.build().unwrap();
+        assert_eq!(tree.count_of(&(5..5)), 1);
+        assert!(tree.find_with_point(5).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "an empty interval at the largest point")]
+    fn panic_build_empty_interval_at_max() {
+        let mut builder = Builder::new();
+        builder.extend(vec![200u8..255, 255..255]);
+        builder.build();
+    }
+}
@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+/// Compact serializable representation of an [`IntervalTree`](struct.IntervalTree.html).
+///
+/// Only the endpoints of the stored intervals are kept, as two parallel arrays, along with the
+/// range of the tree. The tree structure is rebuilt by re-inserting the intervals.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompactTree<I> {
+    pub range: (I, I),
+    pub begins: Vec<I>,
+    pub ends: Vec<I>,
+}
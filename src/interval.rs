@@ -1,16 +1,72 @@
 use std::{
     cmp::Ordering,
     hash::Hash,
-    ops::{Deref, Range},
+    ops::{Deref, Range, RangeFrom, RangeFull, RangeTo},
 };
 
 /// Interval.
-pub trait Interval: Clone + Eq + Hash + Iterator {
+///
+/// Intervals need not implement `Eq` and `Hash` to be stored in an
+/// [`IntervalTree`](struct.IntervalTree.html). Only the methods collecting results into hash sets
+/// require them.
+///
+/// `Range`s of the primitive integers and of `char` implement this trait. Other `Range`s cannot:
+/// `Range<S>` is only an `Iterator` if `S` implements the unstable `Step` trait, so integer-like
+/// newtypes are better wrapped in an interval type of their own implementing this trait.
+pub trait Interval: Clone + PartialEq + Iterator {
+    fn from_bounds(begin: Self::Item, end: Self::Item) -> Self;
+
     fn begin(&self) -> Self::Item;
     fn end(&self) -> Self::Item;
 
     fn center(&self) -> Self::Item;
 
+    fn length(&self) -> Self::Item;
+
+    fn intersect(&self, other: &Self) -> Option<Self>
+    where
+        Self::Item: PartialOrd,
+    {
+        let (begin, end) = (self.begin(), self.end());
+        let (other_begin, other_end) = (other.begin(), other.end());
+        let begin = if other_begin > begin {
+            other_begin
+        } else {
+            begin
+        };
+        let end = if other_end < end { other_end } else { end };
+        if begin < end {
+            Some(Self::from_bounds(begin, end))
+        } else {
+            None
+        }
+    }
+
+    fn contains_point(&self, point: &Self::Item) -> bool
+    where
+        Self::Item: PartialOrd,
+    {
+        self.begin() <= *point && *point < self.end()
+    }
+
+    fn overlaps(&self, other: &Self) -> bool
+    where
+        Self::Item: PartialOrd,
+    {
+        self.intersect(other).is_some()
+    }
+
+    fn overlap_length(&self, other: &Self) -> Self::Item
+    where
+        Self::Item: PartialOrd,
+    {
+        match self.intersect(other) {
+            Some(common) => common.length(),
+            // The length of an empty interval is zero.
+            None => Self::from_bounds(self.begin(), self.begin()).length(),
+        }
+    }
+
     fn left_half(&self) -> Self;
     fn right_half(&self) -> Self;
 
@@ -18,12 +74,51 @@ pub trait Interval: Clone + Eq + Hash + Iterator {
     fn to_end_sorted(&self) -> EndSorted<Self>;
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
-pub struct BeginSorted<T: Interval>(T);
+// Also carries the insertion sequence number of the interval, which does not affect ordering.
+#[derive(Clone, Debug)]
+pub struct BeginSorted<T: Interval>(T, u64);
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug)]
 pub struct EndSorted<T: Interval>(T);
 
+// Equal exactly when `Ord` says so, ignoring the sequence number, so that the intervals need not
+// be `Eq` themselves.
+impl<T: Interval> PartialEq for BeginSorted<T>
+where
+    Self: Ord,
+{
+    fn eq(&self, rhs: &Self) -> bool {
+        self.cmp(rhs) == Ordering::Equal
+    }
+}
+
+impl<T: Interval> Eq for BeginSorted<T> where Self: Ord {}
+
+impl<T: Interval> PartialEq for EndSorted<T>
+where
+    Self: Ord,
+{
+    fn eq(&self, rhs: &Self) -> bool {
+        self.cmp(rhs) == Ordering::Equal
+    }
+}
+
+impl<T: Interval> Eq for EndSorted<T> where Self: Ord {}
+
+impl<T: Interval> BeginSorted<T> {
+    pub(crate) fn into_inner(self) -> T {
+        self.0
+    }
+
+    pub(crate) fn with_sequence(self, sequence: u64) -> Self {
+        Self(self.0, sequence)
+    }
+
+    pub(crate) fn sequence(&self) -> u64 {
+        self.1
+    }
+}
+
 impl<T: Interval> Deref for BeginSorted<T> {
     type Target = T;
 
@@ -32,6 +127,12 @@ impl<T: Interval> Deref for BeginSorted<T> {
     }
 }
 
+impl<T: Interval> EndSorted<T> {
+    pub(crate) fn into_inner(self) -> T {
+        self.0
+    }
+}
+
 impl<T: Interval> Deref for EndSorted<T> {
     type Target = T;
 
@@ -40,9 +141,198 @@ impl<T: Interval> Deref for EndSorted<T> {
     }
 }
 
+/// [`Interval`](trait.Interval.html) tagged with an identifier.
+///
+/// Two `IdInterval`s with the same bounds but different ids are distinct, so both can be stored
+/// in and found from an [`IntervalTree`](struct.IntervalTree.html).
+///
+/// # Examples
+///
+/// ```rust
+/// use interval_tree::{IdInterval, Interval, IntervalTree};
+///
+/// let mut tree = IntervalTree::new(IdInterval::new(0..100, 0));
+///
+/// tree.insert(IdInterval::new(10..20, 1));
+/// tree.insert(IdInterval::new(10..20, 2));
+///
+/// let found = tree.find_with_point(15);
+/// assert_eq!(found.len(), 2);
+/// assert!(found.contains(&IdInterval::new(10..20, 1)));
+/// assert!(found.contains(&IdInterval::new(10..20, 2)));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct IdInterval<T, Id> {
+    pub interval: T,
+    pub id: Id,
+}
+
+impl<T, Id> IdInterval<T, Id> {
+    /// Creates an interval `interval` identified by `id`.
+    pub fn new(interval: T, id: Id) -> Self {
+        Self { interval, id }
+    }
+}
+
+impl<T: Interval, Id> Iterator for IdInterval<T, Id> {
+    type Item = T::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.interval.next()
+    }
+}
+
+impl<T, Id> Interval for IdInterval<T, Id>
+where
+    T: Interval,
+    Id: Clone + Default + PartialEq,
+{
+    /// Creates an interval identified by `Id::default()`.
+    fn from_bounds(begin: Self::Item, end: Self::Item) -> Self {
+        Self::new(T::from_bounds(begin, end), Id::default())
+    }
+
+    fn begin(&self) -> Self::Item {
+        self.interval.begin()
+    }
+
+    fn end(&self) -> Self::Item {
+        self.interval.end()
+    }
+
+    fn center(&self) -> Self::Item {
+        self.interval.center()
+    }
+
+    fn length(&self) -> Self::Item {
+        self.interval.length()
+    }
+
+    /// Intersects the bounds, keeping the id of `self`.
+    fn intersect(&self, other: &Self) -> Option<Self>
+    where
+        Self::Item: PartialOrd,
+    {
+        self.interval
+            .intersect(&other.interval)
+            .map(|interval| Self::new(interval, self.id.clone()))
+    }
+
+    fn left_half(&self) -> Self {
+        Self::new(self.interval.left_half(), self.id.clone())
+    }
+
+    fn right_half(&self) -> Self {
+        Self::new(self.interval.right_half(), self.id.clone())
+    }
+
+    fn to_begin_sorted(&self) -> BeginSorted<Self> {
+        BeginSorted(self.clone(), 0)
+    }
+
+    fn to_end_sorted(&self) -> EndSorted<Self> {
+        EndSorted(self.clone())
+    }
+}
+
+impl<T, Id> Ord for BeginSorted<IdInterval<T, Id>>
+where
+    T: Interval,
+    T::Item: Ord,
+    Id: Clone + Default + PartialEq,
+{
+    fn cmp(&self, rhs: &Self) -> Ordering {
+        self.begin().cmp(&rhs.begin())
+    }
+}
+
+impl<T, Id> PartialOrd for BeginSorted<IdInterval<T, Id>>
+where
+    T: Interval,
+    T::Item: Ord,
+    Id: Clone + Default + PartialEq,
+{
+    fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
+        Some(self.cmp(rhs))
+    }
+}
+
+impl<T, Id> Ord for EndSorted<IdInterval<T, Id>>
+where
+    T: Interval,
+    T::Item: Ord,
+    Id: Clone + Default + PartialEq,
+{
+    fn cmp(&self, rhs: &Self) -> Ordering {
+        rhs.end().cmp(&self.end())
+    }
+}
+
+impl<T, Id> PartialOrd for EndSorted<IdInterval<T, Id>>
+where
+    T: Interval,
+    T::Item: Ord,
+    Id: Clone + Default + PartialEq,
+{
+    fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
+        Some(self.cmp(rhs))
+    }
+}
+
+/// Interval whose beginning, end, or both may be left open.
+///
+/// An open side extends to the corresponding bound of the range of the
+/// [`IntervalTree`](struct.IntervalTree.html) it is inserted to. See
+/// [`IntervalTree::insert_open`](struct.IntervalTree.html#method.insert_open).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct OpenInterval<I> {
+    pub begin: Option<I>,
+    pub end: Option<I>,
+}
+
+impl<I> From<Range<I>> for OpenInterval<I> {
+    fn from(range: Range<I>) -> Self {
+        Self {
+            begin: Some(range.start),
+            end: Some(range.end),
+        }
+    }
+}
+
+impl<I> From<RangeFrom<I>> for OpenInterval<I> {
+    fn from(range: RangeFrom<I>) -> Self {
+        Self {
+            begin: Some(range.start),
+            end: None,
+        }
+    }
+}
+
+impl<I> From<RangeTo<I>> for OpenInterval<I> {
+    fn from(range: RangeTo<I>) -> Self {
+        Self {
+            begin: None,
+            end: Some(range.end),
+        }
+    }
+}
+
+impl<I> From<RangeFull> for OpenInterval<I> {
+    fn from(_: RangeFull) -> Self {
+        Self {
+            begin: None,
+            end: None,
+        }
+    }
+}
+
 macro_rules! impl_interval_for_range {
     ($int:ty) => {
         impl Interval for Range<$int> {
+            fn from_bounds(begin: Self::Item, end: Self::Item) -> Self {
+                begin..end
+            }
+
             fn begin(&self) -> Self::Item {
                 self.start
             }
@@ -55,6 +345,14 @@ macro_rules! impl_interval_for_range {
                 (self.start + self.end) / 2
             }
 
+            fn length(&self) -> Self::Item {
+                if self.start < self.end {
+                    self.end - self.start
+                } else {
+                    0
+                }
+            }
+
             fn left_half(&self) -> Self {
                 self.begin()..self.center()
             }
@@ -64,7 +362,7 @@ macro_rules! impl_interval_for_range {
             }
 
             fn to_begin_sorted(&self) -> BeginSorted<Self> {
-                BeginSorted(self.clone())
+                BeginSorted(self.clone(), 0)
             }
 
             fn to_end_sorted(&self) -> EndSorted<Self> {
@@ -110,3 +408,274 @@ impl_interval_for_range!(u128);
 impl_interval_for_range!(i128);
 impl_interval_for_range!(usize);
 impl_interval_for_range!(isize);
+
+// Code points skipping the surrogates, so that any index in between two `char`s is a `char` too.
+fn char_to_index(c: char) -> u32 {
+    match c as u32 {
+        n if n >= 0xE000 => n - 0x800,
+        n => n,
+    }
+}
+
+fn char_from_index(index: u32) -> char {
+    let n = if index >= 0xD800 {
+        index + 0x800
+    } else {
+        index
+    };
+    char::from_u32(n).unwrap()
+}
+
+/// Ranges of `char`s are split at the middle `char`, not counting the surrogates, which are not
+/// `char`s. The length is represented as the `char` that many `char`s past `'\0'`.
+impl Interval for Range<char> {
+    fn from_bounds(begin: Self::Item, end: Self::Item) -> Self {
+        begin..end
+    }
+
+    fn begin(&self) -> Self::Item {
+        self.start
+    }
+
+    fn end(&self) -> Self::Item {
+        self.end
+    }
+
+    fn center(&self) -> Self::Item {
+        char_from_index((char_to_index(self.start) + char_to_index(self.end)) / 2)
+    }
+
+    fn length(&self) -> Self::Item {
+        if self.start < self.end {
+            char_from_index(char_to_index(self.end) - char_to_index(self.start))
+        } else {
+            '\0'
+        }
+    }
+
+    fn left_half(&self) -> Self {
+        self.begin()..self.center()
+    }
+
+    fn right_half(&self) -> Self {
+        self.center()..self.end()
+    }
+
+    fn to_begin_sorted(&self) -> BeginSorted<Self> {
+        BeginSorted(self.clone(), 0)
+    }
+
+    fn to_end_sorted(&self) -> EndSorted<Self> {
+        EndSorted(self.clone())
+    }
+}
+
+impl Ord for BeginSorted<Range<char>> {
+    fn cmp(&self, rhs: &Self) -> Ordering {
+        self.start.cmp(&rhs.start)
+    }
+}
+
+impl PartialOrd for BeginSorted<Range<char>> {
+    fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
+        Some(self.cmp(rhs))
+    }
+}
+
+impl Ord for EndSorted<Range<char>> {
+    fn cmp(&self, rhs: &Self) -> Ordering {
+        rhs.end.cmp(&self.end)
+    }
+}
+
+impl PartialOrd for EndSorted<Range<char>> {
+    fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
+        Some(self.cmp(rhs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn length() {
+        assert_eq!((3..8).length(), 5);
+        assert_eq!((3..3).length(), 0);
+        assert_eq!((-5i32..5).length(), 10);
+    }
+
+    #[test]
+    fn char_range() {
+        assert_eq!(('a'..'e').center(), 'c');
+        assert_eq!(('a'..'e').length(), '\u{4}');
+        assert_eq!(('a'..'e').overlap_length(&('c'..'z')), '\u{2}');
+        assert_eq!(('a'..'a').length(), '\0');
+
+        // 0xD800..0xE000 are surrogates.
+        assert_eq!(('\u{D700}'..'\u{E100}').center(), '\u{E000}');
+        assert_eq!(('\u{D7FF}'..'\u{E000}').length(), '\u{1}');
+        assert_eq!(('\u{D7FF}'..'\u{E001}').center(), '\u{E000}');
+        assert_eq!(('\u{D7FE}'..'\u{E000}').center(), '\u{D7FF}');
+        assert_eq!(('\0'..char::MAX).length(), char::MAX);
+    }
+
+    #[test]
+    fn intersect() {
+        assert_eq!((0..10).intersect(&(5..15)), Some(5..10));
+        assert_eq!((0..10).intersect(&(3..7)), Some(3..7));
+        assert_eq!((0..5).intersect(&(5..10)), None);
+        assert_eq!((0..5).intersect(&(7..10)), None);
+    }
+
+    #[test]
+    fn overlap_length() {
+        assert_eq!((0..10).overlap_length(&(0..10)), 10);
+        assert_eq!((0..10).overlap_length(&(3..7)), 4);
+        assert_eq!((0..10).overlap_length(&(5..15)), 5);
+        assert_eq!((5..15).overlap_length(&(0..10)), 5);
+        assert_eq!((0..5).overlap_length(&(5..10)), 0);
+        assert_eq!((0..5).overlap_length(&(7..10)), 0);
+
+        // Falls back on the default through `intersect`.
+        let float = FloatInterval(0.0, 1.0);
+        assert_eq!(float.overlap_length(&FloatInterval(0.5, 2.0)), 0.5);
+        assert_eq!(float.overlap_length(&FloatInterval(1.0, 2.0)), 0.0);
+    }
+
+    #[test]
+    fn overlaps() {
+        assert!((0..10).overlaps(&(5..15)));
+        assert!((5..15).overlaps(&(0..10)));
+        assert!((0..10).overlaps(&(3..7)));
+        assert!(!(0..5).overlaps(&(5..10)));
+        assert!(!(5..10).overlaps(&(0..5)));
+        assert!(!(0..5).overlaps(&(7..10)));
+        assert!(!(0..10).overlaps(&(3..3)));
+    }
+
+    #[test]
+    fn contains_point() {
+        assert!((3..8).contains_point(&3));
+        assert!((3..8).contains_point(&7));
+        assert!(!(3..8).contains_point(&8));
+        assert!(!(3..8).contains_point(&2));
+        assert!(!(3..3).contains_point(&3));
+    }
+
+    #[test]
+    fn sorted_eq_matches_ord() {
+        let a = (3..8).to_begin_sorted();
+        let b = (3..5).to_begin_sorted().with_sequence(7);
+        assert_eq!(a.cmp(&b), Ordering::Equal);
+        assert_eq!(a, b);
+        assert_ne!(a, (4..8).to_begin_sorted());
+
+        let a = (3..8).to_end_sorted();
+        assert_eq!(a, (5..8).to_end_sorted());
+        assert_ne!(a, (3..9).to_end_sorted());
+
+        let a = FloatInterval(1.0, 2.0).to_begin_sorted();
+        assert_eq!(a, FloatInterval(1.0, 3.0).to_begin_sorted());
+        assert_ne!(a, FloatInterval(1.5, 2.0).to_begin_sorted());
+    }
+
+    // Interval over `f64`, which is `PartialEq` but not `Eq` nor `Hash`.
+    #[derive(Clone, Debug, PartialEq)]
+    struct FloatInterval(f64, f64);
+
+    impl Iterator for FloatInterval {
+        type Item = f64;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            None
+        }
+    }
+
+    impl Interval for FloatInterval {
+        fn from_bounds(begin: f64, end: f64) -> Self {
+            FloatInterval(begin, end)
+        }
+
+        fn begin(&self) -> f64 {
+            self.0
+        }
+
+        fn end(&self) -> f64 {
+            self.1
+        }
+
+        fn center(&self) -> f64 {
+            (self.0 + self.1) / 2.0
+        }
+
+        fn length(&self) -> f64 {
+            (self.1 - self.0).max(0.0)
+        }
+
+        fn left_half(&self) -> Self {
+            FloatInterval(self.0, self.center())
+        }
+
+        fn right_half(&self) -> Self {
+            FloatInterval(self.center(), self.1)
+        }
+
+        fn to_begin_sorted(&self) -> BeginSorted<Self> {
+            BeginSorted(self.clone(), 0)
+        }
+
+        fn to_end_sorted(&self) -> EndSorted<Self> {
+            EndSorted(self.clone())
+        }
+    }
+
+    impl Ord for BeginSorted<FloatInterval> {
+        fn cmp(&self, rhs: &Self) -> Ordering {
+            self.0 .0.partial_cmp(&rhs.0 .0).unwrap()
+        }
+    }
+
+    impl PartialOrd for BeginSorted<FloatInterval> {
+        fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
+            Some(self.cmp(rhs))
+        }
+    }
+
+    impl Ord for EndSorted<FloatInterval> {
+        fn cmp(&self, rhs: &Self) -> Ordering {
+            rhs.0 .1.partial_cmp(&self.0 .1).unwrap()
+        }
+    }
+
+    impl PartialOrd for EndSorted<FloatInterval> {
+        fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
+            Some(self.cmp(rhs))
+        }
+    }
+
+    #[test]
+    fn query_partial_eq_interval() {
+        use crate::IntervalTree;
+
+        let mut tree = IntervalTree::new(FloatInterval(0.0, 10.0));
+        tree.insert(FloatInterval(1.0, 2.5));
+        tree.insert(FloatInterval(2.0, 6.0));
+        tree.insert(FloatInterval(7.5, 9.0));
+
+        let mut found = Vec::new();
+        tree.for_each_with_point(2.2, |intv| found.push(intv.clone()));
+        found.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        assert_eq!(
+            found,
+            vec![FloatInterval(1.0, 2.5), FloatInterval(2.0, 6.0)]
+        );
+
+        let found: Vec<_> = tree.find_with_point_iter(8.0).collect();
+        assert_eq!(found, vec![&FloatInterval(7.5, 9.0)]);
+
+        assert!(tree.overlaps_any_point(5.5));
+        assert!(!tree.overlaps_any_point(7.0));
+        assert_eq!(tree.innermost_at(2.2), Some(&FloatInterval(1.0, 2.5)));
+    }
+}
@@ -1,10 +1,42 @@
-use std::collections::{BinaryHeap, HashSet};
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, BinaryHeap, HashMap, HashSet},
+    convert::TryFrom,
+    error::Error,
+    fmt::{self, Debug, Display},
+    hash::Hash,
+    iter, mem,
+    num::ParseIntError,
+    ops::{Add, Range, Sub},
+};
 
-use crate::interval::{BeginSorted, EndSorted, Interval};
+#[cfg(feature = "serde")]
+use crate::compact::CompactTree;
+use crate::interval::{BeginSorted, EndSorted, Interval, OpenInterval};
 
 /// Interval tree.
-#[derive(Clone, Debug)]
 pub struct IntervalTree<T>
+where
+    T: Interval,
+    BeginSorted<T>: Ord,
+    EndSorted<T>: Ord,
+{
+    root: Node<T>,
+
+    compact: bool,
+    capacity_limit: Option<usize>,
+    options: Options,
+
+    // Sequence number given to the next interval inserted to this tree.
+    next_sequence: u64,
+    // Number of intervals stored in all the nodes.
+    len: usize,
+}
+
+// Node of an interval tree, storing the intervals that straddle its center. Settings of the whole
+// tree are kept in `IntervalTree` rather than in every node.
+#[derive(Clone, Debug)]
+struct Node<T>
 where
     T: Interval,
     BeginSorted<T>: Ord,
@@ -13,13 +45,205 @@ where
     range: T,
     center: T::Item,
 
-    left: Option<Box<IntervalTree<T>>>,
-    right: Option<Box<IntervalTree<T>>>,
+    left: Option<Box<Node<T>>>,
+    right: Option<Box<Node<T>>>,
 
+    // A compact tree leaves `overlaps_end` empty, keeping its intervals only in `overlaps_begin`.
     overlaps_begin: BinaryHeap<BeginSorted<T>>,
     overlaps_end: BinaryHeap<EndSorted<T>>,
 }
 
+// Not derived, as deriving would not require the bound on `T::Item` that `Node` needs.
+impl<T> Clone for IntervalTree<T>
+where
+    T: Interval,
+    T::Item: Clone,
+    BeginSorted<T>: Ord,
+    EndSorted<T>: Ord,
+{
+    fn clone(&self) -> Self {
+        Self {
+            root: self.root.clone(),
+            compact: self.compact,
+            capacity_limit: self.capacity_limit,
+            options: self.options,
+            next_sequence: self.next_sequence,
+            len: self.len,
+        }
+    }
+}
+
+impl<T> Debug for IntervalTree<T>
+where
+    T: Interval + Debug,
+    T::Item: Debug,
+    BeginSorted<T>: Ord,
+    EndSorted<T>: Ord,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IntervalTree")
+            .field("root", &self.root)
+            .field("compact", &self.compact)
+            .field("capacity_limit", &self.capacity_limit)
+            .field("options", &self.options)
+            .field("next_sequence", &self.next_sequence)
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+/// Insertion policies of an [`IntervalTree`](struct.IntervalTree.html), given to
+/// [`IntervalTree::with_options`](struct.IntervalTree.html#method.with_options).
+///
+/// The default allows both empty and duplicate intervals.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Options {
+    /// Whether empty intervals, such as `5..5`, are inserted.
+    pub allow_empty: bool,
+    /// Whether an interval equal to one already in the tree is inserted.
+    pub allow_duplicates: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            allow_empty: true,
+            allow_duplicates: true,
+        }
+    }
+}
+
+/// Statistics of a node in an [`IntervalTree`](struct.IntervalTree.html).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NodeStat<I> {
+    /// Depth of the node, where the root is at depth zero.
+    pub depth: usize,
+    /// Center of the range of the node.
+    pub center: I,
+    /// Number of intervals stored in the node.
+    pub interval_count: usize,
+}
+
+/// [`Interval`](trait.Interval.html)s around a point, classified by
+/// [`IntervalTree::point_events`](struct.IntervalTree.html#method.point_events).
+#[derive(Clone, Debug)]
+pub struct PointEvents<R> {
+    /// Intervals beginning at the point.
+    pub beginning: HashSet<R>,
+    /// Intervals ending at the point.
+    pub ending: HashSet<R>,
+    /// Intervals beginning before and ending after the point.
+    pub ongoing: HashSet<R>,
+}
+
+/// [`Interval`](trait.Interval.html)s containing a point, split by where they are stored, given
+/// by [`IntervalTree::find_with_point_by_branch`](struct.IntervalTree.html#method.find_with_point_by_branch).
+#[derive(Clone, Debug)]
+pub struct BranchResult<R> {
+    /// Intervals stored in the root node.
+    pub node: HashSet<R>,
+    /// Intervals stored in the left subtree of the root.
+    pub left: HashSet<R>,
+    /// Intervals stored in the right subtree of the root.
+    pub right: HashSet<R>,
+}
+
+/// Third of an [`Interval`](trait.Interval.html) a point falls in, given by
+/// [`IntervalTree::find_with_point_positions`](struct.IntervalTree.html#method.find_with_point_positions).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Position {
+    /// The first third of the interval.
+    Start,
+    /// The middle third of the interval.
+    Middle,
+    /// The last third of the interval.
+    End,
+}
+
+/// Statistics of the number of [`Interval`](trait.Interval.html)s covering each point of the
+/// range of an [`IntervalTree`](struct.IntervalTree.html), given by
+/// [`IntervalTree::depth_stats`](struct.IntervalTree.html#method.depth_stats).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DepthStats {
+    /// Smallest depth, which is zero if any point is left uncovered.
+    pub min: usize,
+    /// Largest depth.
+    pub max: usize,
+    /// Average depth weighted by the number of points, as
+    /// [`IntervalTree::average_depth`](struct.IntervalTree.html#method.average_depth).
+    pub mean: f64,
+}
+
+/// Error of [`IntervalTree::try_from_str_pairs`](struct.IntervalTree.html#method.try_from_str_pairs)
+/// telling which pair was rejected and why.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    /// An endpoint could not be parsed.
+    InvalidEndpoint {
+        /// Position of the offending pair in the input, counting from zero.
+        line: usize,
+        /// Offending endpoint of the pair: `0` for the beginning and `1` for the end.
+        field: usize,
+        /// Error returned by `str::parse`.
+        source: ParseIntError,
+    },
+    /// The beginning of the pair is greater than its end.
+    Reversed {
+        /// Position of the offending pair in the input, counting from zero.
+        line: usize,
+    },
+    /// The interval overflows the range of the tree.
+    OutOfRange {
+        /// Position of the offending pair in the input, counting from zero.
+        line: usize,
+    },
+}
+
+impl ParseError {
+    /// Returns the position of the offending pair in the input, counting from zero.
+    pub fn line(&self) -> usize {
+        match *self {
+            ParseError::InvalidEndpoint { line, .. }
+            | ParseError::Reversed { line }
+            | ParseError::OutOfRange { line } => line,
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::InvalidEndpoint {
+                line,
+                field,
+                source,
+            } => {
+                let field = if *field == 0 { "beginning" } else { "end" };
+                write!(
+                    f,
+                    "invalid {} of the interval at line {}: {}",
+                    field, line, source
+                )
+            }
+            ParseError::Reversed { line } => {
+                write!(f, "reversed interval at line {}", line)
+            }
+            ParseError::OutOfRange { line } => {
+                write!(f, "out-of-range interval at line {}", line)
+            }
+        }
+    }
+}
+
+impl Error for ParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ParseError::InvalidEndpoint { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
 impl<T> IntervalTree<T>
 where
     T: Interval,
@@ -29,17 +253,102 @@ where
 {
     /// Creates a interval tree on `range`.
     pub fn new(range: T) -> Self {
-        let center = range.center();
+        Self {
+            root: Node::new(range),
+
+            compact: false,
+            capacity_limit: None,
+            options: Options::default(),
+
+            next_sequence: 0,
+            len: 0,
+        }
+    }
 
+    /// Creates a interval tree on `range` that stores each interval only once.
+    ///
+    /// By default, every interval is kept in two heaps per node, one sorted by beginning and one
+    /// sorted by end. A compact tree omits the latter, roughly halving the memory spent on
+    /// intervals, at the cost of scanning the whole node where a query would look at ends.
+    /// Queries return the same results as on a default tree.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new_compact(0..100);
+    /// tree.insert(5..10);
+    /// tree.insert(40..60);
+    /// tree.insert(85..95);
+    ///
+    /// assert_eq!(tree.find_with_point(55), [&(40..60)].iter().cloned().collect());
+    /// assert_eq!(tree.find_with_point(90), [&(85..95)].iter().cloned().collect());
+    /// ```
+    pub fn new_compact(range: T) -> Self {
         Self {
-            range,
-            center,
+            compact: true,
+            ..Self::new(range)
+        }
+    }
 
-            left: None,
-            right: None,
+    /// Creates a interval tree on `range` holding at most `limit` intervals.
+    ///
+    /// Once an insertion makes the number of intervals exceed `limit`, the intervals that end the
+    /// earliest are evicted.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::with_capacity_limit(0..100, 2);
+    ///
+    /// tree.insert(40..60);
+    /// tree.insert(5..10);
+    /// tree.insert(85..95); // evicts 5..10
+    ///
+    /// assert!(tree.find_with_point(5).is_empty());
+    /// assert_eq!(tree.find_with_point(50), [&(40..60)].iter().cloned().collect());
+    /// assert_eq!(tree.find_with_point(90), [&(85..95)].iter().cloned().collect());
+    /// ```
+    pub fn with_capacity_limit(range: T, limit: usize) -> Self {
+        Self {
+            capacity_limit: Some(limit),
+            ..Self::new(range)
+        }
+    }
 
-            overlaps_begin: BinaryHeap::new(),
-            overlaps_end: BinaryHeap::new(),
+    /// Creates a interval tree on `range` whose insertions follow `options`.
+    ///
+    /// Intervals rejected by `options` are silently not inserted, by
+    /// [`insert`](#method.insert) as well as by the other insertion methods.
+    ///
+    /// An empty interval contains no point, so it is never found by point queries. It is stored
+    /// in the node centered at its position, which must be in the range of this interval tree.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree, Options};
+    ///
+    /// let options = Options {
+    ///     allow_empty: false,
+    ///     allow_duplicates: false,
+    /// };
+    /// let mut tree = IntervalTree::with_options(0..100, options);
+    ///
+    /// tree.insert(40..60);
+    /// tree.insert(40..60); // duplicate
+    /// tree.insert(50..50); // empty
+    ///
+    /// assert_eq!(tree.find_with_point_iter(50).count(), 1);
+    /// assert_eq!(tree.find_with_interval(40..60).len(), 1);
+    /// ```
+    pub fn with_options(range: T, options: Options) -> Self {
+        Self {
+            options,
+            ..Self::new(range)
         }
     }
 
@@ -57,86 +366,169 @@ where
     ///
     /// # Panic
     ///
-    /// Panics if the interval overflows the range of this interval tree.
+    /// Panics if the interval overflows the range of this interval tree, or if it is empty and
+    /// its position is out-of-range.
     pub fn insert(&mut self, interval: T) {
-        assert!(!self.overflow_interval(&interval));
+        assert!(!self.overflow_insertion(&interval));
 
-        if interval.end() <= self.center {
-            let range = self.range.left_half();
-            self.left
-                .get_or_insert(Box::new(IntervalTree::new(range)))
-                .insert(interval);
-        } else if interval.begin() > self.center {
-            let range = self.range.right_half();
-            self.right
-                .get_or_insert(Box::new(IntervalTree::new(range)))
-                .insert(interval);
-        } else {
-            self.overlaps_begin.push(interval.to_begin_sorted());
-            self.overlaps_end.push(interval.to_end_sorted());
+        if !self.accepts(&interval) {
+            return;
         }
+
+        let sequence = self.next_sequence;
+        self.next_sequence += 1;
+        self.root.insert_rec(interval, sequence, self.compact);
+        self.len += 1;
+
+        self.enforce_capacity_limit();
     }
 
-    /// Finds [`Interval`](trait.Interval.html)s in this interval tree that contain the `point`.
+    /// Rebuilds this interval tree on `range`, keeping all of its
+    /// [`Interval`](trait.Interval.html)s.
     ///
     /// # Examples
     ///
     /// ```rust
-    /// use std::collections::HashSet;
     /// use interval_tree::{Interval, IntervalTree};
     ///
     /// let mut tree = IntervalTree::new(0..100);
-    ///
     /// tree.insert(5..10);
     /// tree.insert(85..95);
-    /// tree.insert(90..100);
-    ///
-    /// assert_eq!(tree.find_with_point(0), HashSet::new());
     ///
-    /// let intervals = [&(5..10)].iter().cloned().collect();
-    /// assert_eq!(tree.find_with_point(5), intervals);
+    /// tree.rebuild_with_range(0..200);
+    /// tree.insert(150..160);
     ///
-    /// let intervals = [&(85..95), &(90..100)].iter().cloned().collect();
-    /// assert_eq!(tree.find_with_point(90), intervals);
+    /// assert_eq!(tree.find_with_point(90), [&(85..95)].iter().cloned().collect());
+    /// assert_eq!(tree.find_with_point(150), [&(150..160)].iter().cloned().collect());
     /// ```
     ///
     /// # Panic
     ///
-    /// Panics if the point is out-of-range of this interval tree.
-    pub fn find_with_point(&self, point: T::Item) -> HashSet<&T> {
-        assert!(!self.overflow_point(&point));
+    /// Panics if any of the intervals overflows `range`. This interval tree is left unchanged in
+    /// that case.
+    pub fn rebuild_with_range(&mut self, range: T) {
+        let mut tree = IntervalTree::new(range);
+        tree.compact = self.compact;
+        tree.capacity_limit = self.capacity_limit;
+        tree.options = self.options;
 
-        let mut found = HashSet::new();
-        self.find_with_point_rec(point, &mut found);
-        found
+        for intv in self.iter_by_insertion() {
+            tree.insert(intv.clone());
+        }
+
+        *self = tree;
     }
 
-    fn find_with_point_rec<'a, 'b>(&'a self, point: T::Item, found: &'b mut HashSet<&'a T>) {
-        if point < self.center {
-            for intv in self
-                .overlaps_begin
-                .iter()
-                .filter(|&intv| intv.begin() <= point)
-            {
-                found.insert(&intv);
-            }
+    /// Inserts an [`Interval`](trait.Interval.html) to this interval tree after clamping it to the
+    /// range of this interval tree.
+    ///
+    /// This is lossy: the parts of `interval` outside the range are discarded, and `interval` is
+    /// not inserted at all if nothing of it remains.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    /// tree.insert_clamped(90..120);
+    /// tree.insert_clamped(100..120);
+    ///
+    /// assert_eq!(tree.find_with_point(95), [&(90..100)].iter().cloned().collect());
+    /// ```
+    pub fn insert_clamped(&mut self, interval: T) {
+        if let Some(interval) = interval.intersect(&self.root.range) {
+            self.insert(interval);
+        }
+    }
 
-            if let Some(ref left) = self.left {
-                left.find_with_point_rec(point, found);
-            }
-        } else {
-            for intv in self.overlaps_end.iter().filter(|intv| intv.end() > point) {
-                found.insert(&intv);
-            }
+    /// Inserts an interval whose beginning or end may be open, such as `..50` or `50..`, to this
+    /// interval tree.
+    ///
+    /// Open sides are resolved against the range of this interval tree at insertion, so the
+    /// stored [`Interval`](trait.Interval.html) extends to the bounds of the range.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    /// tree.insert_open(..50);
+    /// tree.insert_open(50..);
+    ///
+    /// assert_eq!(tree.find_with_point(0), [&(0..50)].iter().cloned().collect());
+    /// assert_eq!(tree.find_with_point(99), [&(50..100)].iter().cloned().collect());
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if the interval overflows the range of this interval tree.
+    pub fn insert_open<I>(&mut self, interval: I)
+    where
+        I: Into<OpenInterval<T::Item>>,
+    {
+        let OpenInterval { begin, end } = interval.into();
+        let begin = begin.unwrap_or_else(|| self.root.range.begin());
+        let end = end.unwrap_or_else(|| self.root.range.end());
 
-            if let Some(ref right) = self.right {
-                right.find_with_point_rec(point, found);
+        self.insert(T::from_bounds(begin, end));
+    }
+
+    /// Inserts [`Interval`](trait.Interval.html)s sorted by their beginnings to this interval
+    /// tree.
+    ///
+    /// The batch is split once per node instead of routing each interval from the root, taking
+    /// advantage of the order: the intervals to go to the right subtree of a node form a suffix
+    /// of the batch, which is split off by a binary search, so that only the rest is scanned. The
+    /// shape of an interval tree depends only on its range and its intervals, so the result is
+    /// the same as inserting them one by one.
+    ///
+    /// `sorted` must be sorted in ascending order of [`begin`](trait.Interval.html#tymethod.begin).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    /// tree.insert(40..60);
+    /// tree.insert_many_sorted(vec![5..10, 45..55, 85..95, 90..100]);
+    ///
+    /// let intervals = [&(40..60), &(45..55)].iter().cloned().collect();
+    /// assert_eq!(tree.find_with_point(50), intervals);
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if any of the intervals overflows the range of this interval tree.
+    pub fn insert_many_sorted(&mut self, sorted: Vec<T>) {
+        debug_assert!(sorted.windows(2).all(|w| w[0].begin() <= w[1].begin()));
+        assert!(sorted.iter().all(|intv| !self.overflow_insertion(intv)));
+
+        let mut accepted: Vec<T> = Vec::with_capacity(sorted.len());
+        for intv in sorted {
+            // Equal intervals begin equally, so they are adjacent to each other in `sorted`.
+            let duplicate = !self.options.allow_duplicates
+                && accepted
+                    .iter()
+                    .rev()
+                    .take_while(|other| other.begin() == intv.begin())
+                    .any(|other| *other == intv);
+            if !duplicate && self.accepts(&intv) {
+                accepted.push(intv);
             }
         }
+
+        let sequences = self.next_sequence..;
+        self.next_sequence += accepted.len() as u64;
+        self.len += accepted.len();
+        self.root
+            .insert_many_sorted_rec(sequences.zip(accepted).collect(), self.compact);
+        self.enforce_capacity_limit();
     }
 
-    /// Finds [`Interval`](trait.Interval.html)s in this interval tree that overlap with
-    /// `interval`.
+    /// Finds [`Interval`](trait.Interval.html)s in this interval tree that contain the `point`.
     ///
     /// # Examples
     ///
@@ -150,86 +542,5776 @@ where
     /// tree.insert(85..95);
     /// tree.insert(90..100);
     ///
-    /// assert_eq!(tree.find_with_interval(0..5), HashSet::new());
+    /// assert_eq!(tree.find_with_point(0), HashSet::new());
     ///
     /// let intervals = [&(5..10)].iter().cloned().collect();
-    /// assert_eq!(tree.find_with_interval(3..8), intervals);
+    /// assert_eq!(tree.find_with_point(5), intervals);
     ///
     /// let intervals = [&(85..95), &(90..100)].iter().cloned().collect();
-    /// assert_eq!(tree.find_with_interval(80..95), intervals);
-    ///
-    /// let intervals = [&(90..100)].iter().cloned().collect();
-    /// assert_eq!(tree.find_with_interval(95..100), intervals);
+    /// assert_eq!(tree.find_with_point(90), intervals);
     /// ```
     ///
     /// # Panic
     ///
-    /// Panics if the interval is out-of-range of this interval tree.
-    pub fn find_with_interval(&self, interval: T) -> HashSet<&T> {
-        assert!(!self.overflow_interval(&interval));
+    /// Panics if the point is out-of-range of this interval tree.
+    pub fn find_with_point(&self, point: T::Item) -> HashSet<&T>
+    where
+        T: Eq + Hash,
+    {
+        assert!(!self.overflow_point(&point));
 
         let mut found = HashSet::new();
-        for p in interval {
-            for intv in self.find_with_point(p) {
-                found.insert(intv);
-            }
-        }
-
+        self.root.for_each_with_point_rec(point, 0, &mut |_, intv| {
+            found.insert(intv);
+        });
         found
     }
 
-    fn overflow_interval(&self, interval: &T) -> bool {
-        interval.begin() < self.range.begin() || interval.end() > self.range.end()
-    }
-
-    fn overflow_point(&self, point: &T::Item) -> bool {
-        point < &self.range.begin() || point >= &self.range.end()
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Calls `f` on each [`Interval`](trait.Interval.html) in this interval tree that contains
+    /// the `point`.
+    ///
+    /// Unlike [`find_with_point`](#method.find_with_point), this does not require the intervals
+    /// to implement `Eq` and `Hash`, and intervals stored more than once are visited as many
+    /// times.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(85..95);
+    /// tree.insert(90..100);
+    ///
+    /// let mut count = 0;
+    /// tree.for_each_with_point(90, |_| count += 1);
+    /// assert_eq!(count, 2);
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if the point is out-of-range of this interval tree.
+    pub fn for_each_with_point<F>(&self, point: T::Item, mut f: F)
+    where
+        F: FnMut(&T),
+    {
+        assert!(!self.overflow_point(&point));
 
-    #[test]
-    #[should_panic]
-    fn panic_insert_begin() {
-        let mut tree = IntervalTree::new(1..11);
-        tree.insert(0..10);
+        self.root
+            .for_each_with_point_rec(point, 0, &mut |_, intv| f(intv));
     }
 
-    #[test]
-    #[should_panic]
-    fn panic_insert_end() {
-        let mut tree = IntervalTree::new(0..10);
-        tree.insert(1..11);
+    /// Returns an iterator over the [`Interval`](trait.Interval.html)s in this interval tree that
+    /// contain the `point`.
+    ///
+    /// Unlike [`find_with_point`](#method.find_with_point), this does not require the intervals
+    /// to implement `Eq` and `Hash`, and intervals stored more than once are yielded as many
+    /// times.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(85..95);
+    /// tree.insert(90..100);
+    ///
+    /// let mut found: Vec<_> = tree.find_with_point_iter(90).collect();
+    /// found.sort_by_key(|intv| intv.start);
+    /// assert_eq!(found, vec![&(85..95), &(90..100)]);
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if the point is out-of-range of this interval tree.
+    pub fn find_with_point_iter(&self, point: T::Item) -> impl Iterator<Item = &T> {
+        assert!(!self.overflow_point(&point));
+
+        let mut found = Vec::new();
+        self.root
+            .for_each_with_point_rec(point, 0, &mut |_, intv| found.push(intv));
+        found.into_iter()
+    }
+
+    /// Folds the [`Interval`](trait.Interval.html)s in this interval tree that contain the
+    /// `point` into an accumulator, without collecting them.
+    ///
+    /// Intervals stored more than once are folded as many times.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(30..70);
+    /// tree.insert(40..60);
+    /// tree.insert(45..55);
+    /// tree.insert(80..90);
+    ///
+    /// let span = tree.fold_with_point(50, 0, |sum, intv| sum + intv.length());
+    /// assert_eq!(span, 40 + 20 + 10);
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if the point is out-of-range of this interval tree.
+    pub fn fold_with_point<B, F>(&self, point: T::Item, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, &T) -> B,
+    {
+        assert!(!self.overflow_point(&point));
+
+        let mut acc = Some(init);
+        self.root.for_each_with_point_rec(point, 0, &mut |_, intv| {
+            acc = acc.take().map(|acc| f(acc, intv));
+        });
+        acc.unwrap()
+    }
+
+    /// Returns whether any [`Interval`](trait.Interval.html) in this interval tree contains the
+    /// `point`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    /// tree.insert(85..95);
+    ///
+    /// assert!(tree.overlaps_any_point(90));
+    /// assert!(!tree.overlaps_any_point(50));
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if the point is out-of-range of this interval tree.
+    pub fn overlaps_any_point(&self, point: T::Item) -> bool {
+        assert!(!self.overflow_point(&point));
+
+        self.root.overlaps_any_point_rec(point)
+    }
+
+    /// Finds [`Interval`](trait.Interval.html)s in this interval tree that contain the `point`,
+    /// returning clones of them.
+    ///
+    /// Unlike [`find_with_point`](#method.find_with_point), the result does not borrow this
+    /// interval tree, so it can be sent to another thread.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::thread;
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(85..95);
+    /// tree.insert(90..100);
+    ///
+    /// let found = tree.find_with_point_owned(90);
+    /// let expected = tree.find_with_point(90).into_iter().cloned().collect();
+    /// assert_eq!(found, expected);
+    ///
+    /// let handle = thread::spawn(move || found.len());
+    /// assert_eq!(handle.join().unwrap(), 2);
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if the point is out-of-range of this interval tree.
+    pub fn find_with_point_owned(&self, point: T::Item) -> HashSet<T>
+    where
+        T: Eq + Hash,
+    {
+        self.find_with_point(point).into_iter().cloned().collect()
+    }
+
+    /// Finds [`Interval`](trait.Interval.html)s in this interval tree that contain the `point`,
+    /// sorted by `key`.
+    ///
+    /// The order of intervals with equal keys is unspecified.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::cmp::Reverse;
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(45..55);
+    /// tree.insert(30..70);
+    /// tree.insert(40..60);
+    ///
+    /// // longest first
+    /// let found = tree.find_with_point_sorted_by_key(50, |intv| Reverse(intv.end - intv.start));
+    /// assert_eq!(found, vec![&(30..70), &(40..60), &(45..55)]);
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if the point is out-of-range of this interval tree.
+    pub fn find_with_point_sorted_by_key<K, F>(&self, point: T::Item, key: F) -> Vec<&T>
+    where
+        T: Eq + Hash,
+        K: Ord,
+        F: Fn(&T) -> K,
+    {
+        let mut found: Vec<_> = self.find_with_point(point).into_iter().collect();
+        found.sort_by_key(|intv| key(intv));
+        found
+    }
+
+    /// Finds [`Interval`](trait.Interval.html)s in this interval tree that contain the `point`,
+    /// grouped by the depth of the node storing them, where the root is at depth zero.
+    ///
+    /// Depths where no interval is found are omitted.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(20..60); // stored in the root, centered at 50
+    /// tree.insert(20..30); // stored in the node on 0..50, centered at 25
+    /// tree.insert(10..14); // stored in the node on 0..25, centered at 12
+    ///
+    /// let found = tree.find_with_point_by_depth(25);
+    /// assert_eq!(found.len(), 2);
+    /// assert_eq!(found[&0], [&(20..60)].iter().cloned().collect());
+    /// assert_eq!(found[&1], [&(20..30)].iter().cloned().collect());
+    ///
+    /// let found = tree.find_with_point_by_depth(12);
+    /// assert_eq!(found.keys().collect::<Vec<_>>(), vec![&2]);
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if the point is out-of-range of this interval tree.
+    pub fn find_with_point_by_depth(&self, point: T::Item) -> BTreeMap<usize, HashSet<&T>>
+    where
+        T: Eq + Hash,
+    {
+        assert!(!self.overflow_point(&point));
+
+        let mut found = BTreeMap::new();
+        self.root
+            .for_each_with_point_rec(point, 0, &mut |depth, intv| {
+                found.entry(depth).or_insert_with(HashSet::new).insert(intv);
+            });
+        found
+    }
+
+    /// Finds [`Interval`](trait.Interval.html)s in this interval tree that contain the `point`,
+    /// split by whether they are stored in the root node or in its left or right subtree.
+    ///
+    /// A point query descends into only one of the subtrees, so either `left` or `right` of the
+    /// result is always empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(40..60); // stored in the root, centered at 50
+    /// tree.insert(20..45); // stored in the left subtree
+    ///
+    /// let found = tree.find_with_point_by_branch(42);
+    /// assert_eq!(found.node, [&(40..60)].iter().cloned().collect());
+    /// assert_eq!(found.left, [&(20..45)].iter().cloned().collect());
+    /// assert!(found.right.is_empty());
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if the point is out-of-range of this interval tree.
+    pub fn find_with_point_by_branch(&self, point: T::Item) -> BranchResult<&T>
+    where
+        T: Eq + Hash,
+    {
+        assert!(!self.overflow_point(&point));
+
+        let mut found = BranchResult {
+            node: HashSet::new(),
+            left: HashSet::new(),
+            right: HashSet::new(),
+        };
+        let to_left = point < self.root.center;
+        self.root
+            .for_each_with_point_rec(point, 0, &mut |depth, intv| {
+                if depth == 0 {
+                    found.node.insert(intv);
+                } else if to_left {
+                    found.left.insert(intv);
+                } else {
+                    found.right.insert(intv);
+                }
+            });
+        found
+    }
+
+    /// Finds [`Interval`](trait.Interval.html)s in this interval tree that contain the `point`,
+    /// sorted by the distance from their centers to the `point`, nearest first.
+    ///
+    /// Ties are broken in favor of the interval that begins first. Intervals stored more than
+    /// once are returned as many times.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(48..60); // centered at 54
+    /// tree.insert(40..60); // centered at 50
+    /// tree.insert(20..52); // centered at 36
+    ///
+    /// let found = tree.find_with_point_by_centeredness(50);
+    /// assert_eq!(found, vec![&(40..60), &(48..60), &(20..52)]);
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if the point is out-of-range of this interval tree.
+    pub fn find_with_point_by_centeredness(&self, point: T::Item) -> Vec<&T>
+    where
+        T::Item: Clone + Sub<Output = T::Item>,
+    {
+        let distance = |intv: &T| {
+            let center = intv.center();
+            if center < point {
+                point.clone() - center
+            } else {
+                center - point.clone()
+            }
+        };
+
+        let mut found: Vec<_> = self.find_with_point_iter(point.clone()).collect();
+        found.sort_by(|a, b| {
+            (distance(a), a.begin())
+                .partial_cmp(&(distance(b), b.begin()))
+                .unwrap()
+        });
+        found
+    }
+
+    /// Finds [`Interval`](trait.Interval.html)s in this interval tree that contain the `point`,
+    /// each with the third of it the `point` falls in.
+    ///
+    /// A point on the boundary of two thirds belongs to the later one. Intervals stored more
+    /// than once are returned as many times.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree, Position};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(0..6);
+    /// tree.insert(3..12);
+    ///
+    /// let mut found = tree.find_with_point_positions(4);
+    /// found.sort_by_key(|(intv, _)| intv.start);
+    /// assert_eq!(found, vec![(&(0..6), Position::End), (&(3..12), Position::Start)]);
+    ///
+    /// let found = tree.find_with_point_positions(2);
+    /// assert_eq!(found, vec![(&(0..6), Position::Middle)]);
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if the point is out-of-range of this interval tree.
+    pub fn find_with_point_positions(&self, point: T::Item) -> Vec<(&T, Position)>
+    where
+        T::Item: Clone + Sub<Output = T::Item>,
+        u128: TryFrom<T::Item>,
+    {
+        let to_u128 = |x: T::Item| u128::try_from(x).ok().unwrap();
+
+        self.find_with_point_iter(point.clone())
+            .map(|intv| {
+                let offset = to_u128(point.clone() - intv.begin());
+                let length = to_u128(intv.length());
+                // `offset * 3 < length` and `offset * 3 < length * 2`, without overflowing.
+                let position = if offset < length / 3 + (length % 3 != 0) as u128 {
+                    Position::Start
+                } else if offset < length - length / 3 {
+                    Position::Middle
+                } else {
+                    Position::End
+                };
+                (intv, position)
+            })
+            .collect()
+    }
+
+    /// Finds [`Interval`](trait.Interval.html)s in this interval tree that contain the `point`
+    /// and are no longer than `max_len`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(0..100);
+    /// tree.insert(40..60);
+    /// tree.insert(45..55);
+    ///
+    /// let intervals = [&(40..60), &(45..55)].iter().cloned().collect();
+    /// assert_eq!(tree.find_with_point_max_len(50, 20), intervals);
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if the point is out-of-range of this interval tree.
+    pub fn find_with_point_max_len(&self, point: T::Item, max_len: T::Item) -> HashSet<&T>
+    where
+        T: Eq + Hash,
+    {
+        assert!(!self.overflow_point(&point));
+
+        let mut found = HashSet::new();
+        self.root.for_each_with_point_rec(point, 0, &mut |_, intv| {
+            if intv.length() <= max_len {
+                found.insert(intv);
+            }
+        });
+        found
+    }
+
+    /// Classifies the [`Interval`](trait.Interval.html)s in this interval tree that begin at,
+    /// end at, or strictly cross the `point`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(40..50);
+    /// tree.insert(50..60);
+    /// tree.insert(45..55);
+    /// tree.insert(10..20);
+    ///
+    /// let events = tree.point_events(50);
+    /// assert_eq!(events.beginning, [&(50..60)].iter().cloned().collect());
+    /// assert_eq!(events.ending, [&(40..50)].iter().cloned().collect());
+    /// assert_eq!(events.ongoing, [&(45..55)].iter().cloned().collect());
+    /// ```
+    pub fn point_events(&self, point: T::Item) -> PointEvents<&T>
+    where
+        T: Eq + Hash,
+    {
+        let mut events = PointEvents {
+            beginning: HashSet::new(),
+            ending: HashSet::new(),
+            ongoing: HashSet::new(),
+        };
+        self.root.point_events_rec(point, &mut events);
+        events
+    }
+
+    /// Returns the [`Interval`](trait.Interval.html) containing the `point` if it is the only one
+    /// in this interval tree to do so.
+    ///
+    /// The search stops as soon as a second interval containing the `point` is found. An interval
+    /// stored more than once counts as many times.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(40..60);
+    /// tree.insert(50..70);
+    ///
+    /// assert_eq!(tree.uniquely_covered(10), None);
+    /// assert_eq!(tree.uniquely_covered(45), Some(&(40..60)));
+    /// assert_eq!(tree.uniquely_covered(55), None);
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if the point is out-of-range of this interval tree.
+    pub fn uniquely_covered(&self, point: T::Item) -> Option<&T> {
+        assert!(!self.overflow_point(&point));
+
+        let mut found = None;
+        if self.root.uniquely_covered_rec(point, &mut found) {
+            found
+        } else {
+            None
+        }
+    }
+
+    /// Finds [`Interval`](trait.Interval.html)s in this interval tree that overlap with
+    /// `interval`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::collections::HashSet;
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(5..10);
+    /// tree.insert(85..95);
+    /// tree.insert(90..100);
+    ///
+    /// assert_eq!(tree.find_with_interval(0..5), HashSet::new());
+    ///
+    /// let intervals = [&(5..10)].iter().cloned().collect();
+    /// assert_eq!(tree.find_with_interval(3..8), intervals);
+    ///
+    /// let intervals = [&(85..95), &(90..100)].iter().cloned().collect();
+    /// assert_eq!(tree.find_with_interval(80..95), intervals);
+    ///
+    /// let intervals = [&(90..100)].iter().cloned().collect();
+    /// assert_eq!(tree.find_with_interval(95..100), intervals);
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if the interval is out-of-range of this interval tree.
+    pub fn find_with_interval(&self, interval: T) -> HashSet<&T>
+    where
+        T: Eq + Hash,
+    {
+        assert!(!self.overflow_interval(&interval));
+
+        let mut found = HashSet::new();
+        for p in interval {
+            for intv in self.find_with_point(p) {
+                found.insert(intv);
+            }
+        }
+
+        found
+    }
+
+    /// Finds [`Interval`](trait.Interval.html)s in this interval tree that contain a point within
+    /// `tolerance` of the `point`.
+    ///
+    /// The points from `point - tolerance` to `point + tolerance`, both inclusive, are searched,
+    /// clamped to the range of this interval tree.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(10..20);
+    /// tree.insert(25..30);
+    ///
+    /// assert!(tree.find_near_point(21, 1).is_empty());
+    /// assert_eq!(tree.find_near_point(21, 2), [&(10..20)].iter().cloned().collect());
+    /// assert_eq!(
+    ///     tree.find_near_point(21, 4),
+    ///     [&(10..20), &(25..30)].iter().cloned().collect()
+    /// );
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if the point is out-of-range of this interval tree.
+    pub fn find_near_point(&self, point: T::Item, tolerance: T::Item) -> HashSet<&T>
+    where
+        T: Eq + Hash,
+        T::Item: Clone + Add<Output = T::Item> + Sub<Output = T::Item>,
+    {
+        assert!(!self.overflow_point(&point));
+
+        // Compared as distances from `point` to avoid overflowing at the ends of the range.
+        let begin = if point.clone() - self.root.range.begin() > tolerance {
+            point.clone() - tolerance.clone()
+        } else {
+            self.root.range.begin()
+        };
+        if self.root.range.end() - point.clone() > tolerance {
+            let last = point + tolerance;
+            let mut found = self.find_with_point(last.clone());
+            found.extend(self.find_with_interval(T::from_bounds(begin, last)));
+            found
+        } else {
+            self.find_with_interval(T::from_bounds(begin, self.root.range.end()))
+        }
+    }
+
+    /// Finds [`Interval`](trait.Interval.html)s in this interval tree that contain every one of
+    /// the `points`.
+    ///
+    /// Every interval contains all of no points, so an empty `points` finds all the intervals.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(10..30);
+    /// tree.insert(20..50);
+    ///
+    /// assert_eq!(
+    ///     tree.find_containing_all(&[25, 40]),
+    ///     [&(20..50)].iter().cloned().collect()
+    /// );
+    /// assert!(tree.find_containing_all(&[15, 40]).is_empty());
+    /// assert_eq!(tree.find_containing_all(&[]).len(), 2);
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if any of the points is out-of-range of this interval tree.
+    pub fn find_containing_all(&self, points: &[T::Item]) -> HashSet<&T>
+    where
+        T: Eq + Hash,
+        T::Item: Clone,
+    {
+        let (first, rest) = match points.split_first() {
+            Some(split) => split,
+            None => return self.iter().collect(),
+        };
+
+        let mut found = self.find_with_point(first.clone());
+        for point in rest {
+            let here = self.find_with_point(point.clone());
+            found.retain(|intv| here.contains(intv));
+        }
+
+        found
+    }
+
+    /// Finds [`Interval`](trait.Interval.html)s in this interval tree that contain any of the
+    /// `points`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(10..30);
+    /// tree.insert(20..50);
+    /// tree.insert(70..80);
+    ///
+    /// assert_eq!(
+    ///     tree.find_containing_any(&[15, 75]),
+    ///     [&(10..30), &(70..80)].iter().cloned().collect()
+    /// );
+    /// assert!(tree.find_containing_any(&[]).is_empty());
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if any of the points is out-of-range of this interval tree.
+    pub fn find_containing_any(&self, points: &[T::Item]) -> HashSet<&T>
+    where
+        T: Eq + Hash,
+        T::Item: Clone,
+    {
+        let mut found = HashSet::new();
+        for point in points {
+            found.extend(self.find_with_point_iter(point.clone()));
+        }
+
+        found
+    }
+
+    /// Finds [`Interval`](trait.Interval.html)s in this interval tree whose ends lie in `window`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(10..40);
+    /// tree.insert(30..50);
+    /// tree.insert(45..60);
+    /// tree.insert(50..70);
+    ///
+    /// let intervals = [&(10..40), &(30..50)].iter().cloned().collect();
+    /// assert_eq!(tree.find_ending_in(40..60), intervals);
+    /// ```
+    pub fn find_ending_in(&self, window: T) -> HashSet<&T>
+    where
+        T: Eq + Hash,
+    {
+        let mut found = HashSet::new();
+        self.root.find_ending_in_rec(&window, &mut found);
+        found
+    }
+
+    /// Finds [`Interval`](trait.Interval.html)s in this interval tree whose beginnings lie in
+    /// `window`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(10..50); // overlaps with the window, but begins before it
+    /// tree.insert(40..50);
+    /// tree.insert(55..90);
+    /// tree.insert(60..70);
+    ///
+    /// let intervals = [&(40..50), &(55..90)].iter().cloned().collect();
+    /// assert_eq!(tree.find_beginning_in(40..60), intervals);
+    /// ```
+    pub fn find_beginning_in(&self, window: T) -> HashSet<&T>
+    where
+        T: Eq + Hash,
+    {
+        let mut found = HashSet::new();
+        self.root.find_beginning_in_rec(&window, &mut found);
+        found
+    }
+
+    /// Finds [`Interval`](trait.Interval.html)s in this interval tree that touch `query` without
+    /// overlapping it, i.e. that end exactly where `query` begins or begin exactly where `query`
+    /// ends.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..20);
+    ///
+    /// tree.insert(0..5);
+    /// tree.insert(3..8);
+    /// tree.insert(10..15);
+    /// tree.insert(12..20);
+    ///
+    /// let intervals = [&(0..5), &(10..15)].iter().cloned().collect();
+    /// assert_eq!(tree.find_touching(5..10), intervals);
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if the interval is out-of-range of this interval tree.
+    pub fn find_touching(&self, query: T) -> HashSet<&T>
+    where
+        T: Eq + Hash,
+    {
+        assert!(!self.overflow_interval(&query));
+
+        self.iter()
+            .filter(|intv| intv.end() == query.begin() || intv.begin() == query.end())
+            .collect()
+    }
+
+    /// Returns the [`Interval`](trait.Interval.html) in this interval tree nearest to `query`
+    /// among those not overlapping it, or `None` if every interval overlaps it.
+    ///
+    /// The distance is the length of the gap between the two intervals, which is zero for
+    /// intervals touching `query`. Ties are broken in favor of the interval inserted first.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(10..20);
+    /// tree.insert(30..45);
+    /// tree.insert(60..70);
+    ///
+    /// assert_eq!(tree.nearest_disjoint(40..50), Some(&(60..70)));
+    /// assert_eq!(tree.nearest_disjoint(0..100), None);
+    /// ```
+    pub fn nearest_disjoint(&self, query: T) -> Option<&T> {
+        let distance = |intv: &T| {
+            if intv.end() <= query.begin() {
+                T::from_bounds(intv.end(), query.begin()).length()
+            } else {
+                T::from_bounds(query.end(), intv.begin()).length()
+            }
+        };
+
+        self.iter_by_insertion()
+            .into_iter()
+            .filter(|intv| !intv.overlaps(&query))
+            .map(|intv| (intv, distance(intv)))
+            .fold(
+                None,
+                |nearest: Option<(&T, T::Item)>, (intv, dist)| match nearest {
+                    Some((_, ref min)) if *min <= dist => nearest,
+                    _ => Some((intv, dist)),
+                },
+            )
+            .map(|(intv, _)| intv)
+    }
+
+    /// Returns the balance factor of this interval tree, defined as `(h_max + 1) / (h_min + 1)`
+    /// where `h_max` and `h_min` are the heights of the taller and shorter subtrees of the root.
+    /// A missing subtree has a height of zero.
+    ///
+    /// The factor is `1.0` when both subtrees have the same height, and grows as the tree gets
+    /// skewed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    /// assert_eq!(tree.balance_factor(), 1.0);
+    ///
+    /// tree.insert(5..10);
+    /// assert_eq!(tree.balance_factor(), 4.0);
+    ///
+    /// tree.insert(90..95);
+    /// assert_eq!(tree.balance_factor(), 1.0);
+    /// ```
+    pub fn balance_factor(&self) -> f64 {
+        let left = self.root.left.as_ref().map_or(0, |left| left.height());
+        let right = self.root.right.as_ref().map_or(0, |right| right.height());
+
+        (left.max(right) + 1) as f64 / (left.min(right) + 1) as f64
+    }
+
+    /// Checks that the internal invariants of this interval tree hold.
+    ///
+    /// The following are verified for every node:
+    ///
+    /// - every interval stored in the node straddles the node's center,
+    /// - every interval in the left subtree ends at or before the center,
+    /// - every interval in the right subtree begins after the center,
+    /// - both heaps of the node contain the same multiset of intervals.
+    ///
+    /// Returns a description of the first violation found.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    /// tree.insert(5..10);
+    /// tree.insert(45..55);
+    /// tree.insert(85..95);
+    ///
+    /// assert_eq!(tree.check_invariants(), Ok(()));
+    /// ```
+    pub fn check_invariants(&self) -> Result<(), String>
+    where
+        T: Eq + Hash,
+        T: Debug,
+        T::Item: Debug,
+    {
+        self.root.check_invariants(self.compact)
+    }
+
+    /// Returns an iterator over the [`Interval`](trait.Interval.html)s in this interval tree,
+    /// each paired with the center of the node storing it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::collections::HashSet;
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(45..55);
+    /// tree.insert(20..30);
+    /// tree.insert(60..90);
+    ///
+    /// // 0..100 is split at 50, 0..50 at 25 and 50..100 at 75.
+    /// let placement: HashSet<_> = tree.iter_with_centers().collect();
+    /// let expected = [(&(45..55), 50), (&(20..30), 25), (&(60..90), 75)];
+    /// assert_eq!(placement, expected.iter().cloned().collect());
+    /// ```
+    pub fn iter_with_centers(&self) -> impl Iterator<Item = (&T, T::Item)> + '_
+    where
+        T::Item: Clone,
+    {
+        self.nodes().flat_map(|node| {
+            node.overlaps_begin
+                .iter()
+                .map(move |intv| (&**intv, node.center.clone()))
+        })
+    }
+
+    /// Returns an iterator over the [`Interval`](trait.Interval.html)s in this interval tree in
+    /// ascending order of their ends.
+    ///
+    /// The intervals are collected into a vector and sorted up front, which allocates as many
+    /// references as there are intervals.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(10..60);
+    /// tree.insert(20..30);
+    /// tree.insert(70..80);
+    ///
+    /// let sorted: Vec<_> = tree.iter_sorted_by_end().collect();
+    /// assert_eq!(sorted, vec![&(20..30), &(10..60), &(70..80)]);
+    /// ```
+    pub fn iter_sorted_by_end(&self) -> impl Iterator<Item = &T> {
+        let mut sorted: Vec<_> = self.iter().collect();
+        sorted.sort_by(|a, b| a.end().partial_cmp(&b.end()).unwrap());
+        sorted.into_iter()
+    }
+
+    /// Returns the maximal sub-ranges of the range of this interval tree where at least
+    /// `threshold` [`Interval`](trait.Interval.html)s overlap, as `(begin, end)` pairs.
+    ///
+    /// A `threshold` of zero is met everywhere, so the whole range is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..10);
+    ///
+    /// tree.insert(0..6);
+    /// tree.insert(2..8);
+    /// tree.insert(4..10);
+    ///
+    /// assert_eq!(tree.regions_over_threshold(3), vec![(4, 6)]);
+    /// assert_eq!(tree.regions_over_threshold(2), vec![(2, 8)]);
+    /// assert_eq!(tree.regions_over_threshold(4), vec![]);
+    /// assert_eq!(tree.regions_over_threshold(0), vec![(0, 10)]);
+    /// ```
+    pub fn regions_over_threshold(&self, threshold: usize) -> Vec<(T::Item, T::Item)> {
+        if threshold == 0 {
+            return vec![(self.root.range.begin(), self.root.range.end())];
+        }
+
+        let mut regions = Vec::new();
+        let mut depth = 0;
+        let mut start = None;
+
+        for (point, delta) in self.depth_changes() {
+            depth += delta;
+            if depth >= threshold as isize {
+                if start.is_none() {
+                    start = Some(point);
+                }
+            } else if let Some(begin) = start.take() {
+                regions.push((begin, point));
+            }
+        }
+
+        regions
+    }
+
+    /// Counts the distinct integer points covered by at least one
+    /// [`Interval`](trait.Interval.html) in this interval tree.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..20);
+    ///
+    /// tree.insert(0..5);
+    /// tree.insert(3..8);
+    /// tree.insert(10..12);
+    ///
+    /// // 0..8 and 10..12
+    /// assert_eq!(tree.covered_points(), 10);
+    /// ```
+    pub fn covered_points(&self) -> u128
+    where
+        T::Item: Sub<Output = T::Item>,
+        u128: TryFrom<T::Item>,
+    {
+        self.regions_over_threshold(1)
+            .into_iter()
+            .map(|(begin, end)| u128::try_from(end - begin).ok().unwrap())
+            .sum()
+    }
+
+    /// Returns a bitmap of the points in the range of this interval tree, where the `i`-th element
+    /// tells whether the point `i` past the beginning of the range is covered by an
+    /// [`Interval`](trait.Interval.html).
+    ///
+    /// Point lookups in the bitmap take constant time, but it costs one byte per point of the
+    /// range, so this is only suitable for small ranges.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(10..16);
+    ///
+    /// tree.insert(10..12);
+    /// tree.insert(11..13);
+    /// tree.insert(15..16);
+    ///
+    /// assert_eq!(
+    ///     tree.to_coverage_bitmap(),
+    ///     vec![true, true, true, false, false, true]
+    /// );
+    /// ```
+    pub fn to_coverage_bitmap(&self) -> Vec<bool>
+    where
+        T::Item: Clone + Sub<Output = T::Item>,
+        u128: TryFrom<T::Item>,
+    {
+        let offset = |point: T::Item| {
+            u128::try_from(point - self.root.range.begin())
+                .ok()
+                .unwrap() as usize
+        };
+
+        let mut bitmap = vec![false; offset(self.root.range.end())];
+        for (begin, end) in self.regions_over_threshold(1) {
+            for covered in &mut bitmap[offset(begin)..offset(end)] {
+                *covered = true;
+            }
+        }
+
+        bitmap
+    }
+
+    /// Finds [`Interval`](trait.Interval.html)s in this interval tree that contain each of
+    /// `points`, querying in parallel.
+    ///
+    /// The `i`-th element of the result corresponds to `points[i]`. Requires the `rayon`
+    /// feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(5..10);
+    /// tree.insert(85..95);
+    /// tree.insert(90..100);
+    ///
+    /// let found = tree.par_find_with_points(&[5, 50, 90]);
+    /// assert_eq!(found[0], [&(5..10)].iter().cloned().collect());
+    /// assert!(found[1].is_empty());
+    /// assert_eq!(found[2], [&(85..95), &(90..100)].iter().cloned().collect());
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if any of the points is out-of-range of this interval tree.
+    #[cfg(feature = "rayon")]
+    pub fn par_find_with_points(&self, points: &[T::Item]) -> Vec<HashSet<&T>>
+    where
+        T: Eq + Hash,
+        T: Sync,
+        T::Item: Clone + Sync,
+    {
+        use rayon::prelude::*;
+
+        points
+            .par_iter()
+            .map(|point| self.find_with_point(point.clone()))
+            .collect()
+    }
+
+    /// Returns the [`Interval`](trait.Interval.html) with the smallest span among those that
+    /// contain the `point`.
+    ///
+    /// Ties are broken in favor of the interval that begins first.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(0..100);
+    /// tree.insert(40..60);
+    /// tree.insert(45..55);
+    ///
+    /// assert_eq!(tree.innermost_at(50), Some(&(45..55)));
+    /// assert_eq!(tree.innermost_at(42), Some(&(40..60)));
+    /// assert_eq!(tree.innermost_at(10), Some(&(0..100)));
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if the point is out-of-range of this interval tree.
+    pub fn innermost_at(&self, point: T::Item) -> Option<&T> {
+        self.find_with_point_iter(point).min_by(|a, b| {
+            (a.length(), a.begin())
+                .partial_cmp(&(b.length(), b.begin()))
+                .unwrap()
+        })
+    }
+
+    /// Returns the [`Interval`](trait.Interval.html) with the largest span among those that
+    /// contain the `point`.
+    ///
+    /// Ties are broken in favor of the interval that begins first.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(30..70);
+    /// tree.insert(40..60);
+    /// tree.insert(45..55);
+    ///
+    /// assert_eq!(tree.outermost_at(50), Some(&(30..70)));
+    /// assert_eq!(tree.outermost_at(10), None);
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if the point is out-of-range of this interval tree.
+    pub fn outermost_at(&self, point: T::Item) -> Option<&T> {
+        self.find_with_point_iter(point).min_by(|a, b| {
+            (b.length(), a.begin())
+                .partial_cmp(&(a.length(), b.begin()))
+                .unwrap()
+        })
+    }
+
+    /// Returns the [`Interval`](trait.Interval.html) that ends the last among those that contain
+    /// the `point`.
+    ///
+    /// Ties are broken in favor of the interval that begins first.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(30..60);
+    /// tree.insert(45..80);
+    /// tree.insert(50..70);
+    ///
+    /// assert_eq!(tree.latest_ending_at(55), Some(&(45..80)));
+    /// assert_eq!(tree.latest_ending_at(10), None);
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if the point is out-of-range of this interval tree.
+    pub fn latest_ending_at(&self, point: T::Item) -> Option<&T> {
+        self.find_with_point_iter(point).min_by(|a, b| {
+            (b.end(), a.begin())
+                .partial_cmp(&(a.end(), b.begin()))
+                .unwrap()
+        })
+    }
+
+    /// Returns the [`Interval`](trait.Interval.html) that begins the first among those that
+    /// contain the `point`.
+    ///
+    /// Ties are broken in favor of the interval that ends last.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(30..60);
+    /// tree.insert(45..80);
+    /// tree.insert(50..70);
+    ///
+    /// assert_eq!(tree.earliest_starting_at(55), Some(&(30..60)));
+    /// assert_eq!(tree.earliest_starting_at(10), None);
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if the point is out-of-range of this interval tree.
+    pub fn earliest_starting_at(&self, point: T::Item) -> Option<&T> {
+        self.find_with_point_iter(point).min_by(|a, b| {
+            (a.begin(), b.end())
+                .partial_cmp(&(b.begin(), a.end()))
+                .unwrap()
+        })
+    }
+
+    /// Returns the smallest interval enclosing all the [`Interval`](trait.Interval.html)s that
+    /// contain the `point`, i.e. from the earliest beginning to the latest end among them, or
+    /// `None` if no interval contains the point.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(30..60);
+    /// tree.insert(45..80);
+    /// tree.insert(10..20);
+    ///
+    /// assert_eq!(tree.enclosing_span_at(50), Some(30..80));
+    /// assert_eq!(tree.enclosing_span_at(15), Some(10..20));
+    /// assert_eq!(tree.enclosing_span_at(25), None);
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if the point is out-of-range of this interval tree.
+    pub fn enclosing_span_at(&self, point: T::Item) -> Option<T> {
+        self.find_with_point_iter(point)
+            .fold(None, |span: Option<(T::Item, T::Item)>, intv| match span {
+                Some((begin, end)) => {
+                    let begin = if intv.begin() < begin {
+                        intv.begin()
+                    } else {
+                        begin
+                    };
+                    let end = if intv.end() > end { intv.end() } else { end };
+                    Some((begin, end))
+                }
+                None => Some((intv.begin(), intv.end())),
+            })
+            .map(|(begin, end)| T::from_bounds(begin, end))
+    }
+
+    /// Converts this interval tree into its compact serializable representation. Requires the
+    /// `serde` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    /// tree.insert(5..10);
+    /// tree.insert(85..95);
+    ///
+    /// let json = serde_json::to_string(&tree.to_compact()).unwrap();
+    /// let tree: IntervalTree<_> = IntervalTree::from_compact(serde_json::from_str(&json).unwrap());
+    ///
+    /// assert_eq!(tree.find_with_point(90), [&(85..95)].iter().cloned().collect());
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn to_compact(&self) -> CompactTree<T::Item> {
+        let (begins, ends) = self.iter().map(|intv| (intv.begin(), intv.end())).unzip();
+
+        CompactTree {
+            range: (self.root.range.begin(), self.root.range.end()),
+            begins,
+            ends,
+        }
+    }
+
+    /// Rebuilds an interval tree from its compact serializable representation. Requires the
+    /// `serde` feature.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `begins` and `ends` differ in length, or if an interval overflows the range.
+    #[cfg(feature = "serde")]
+    pub fn from_compact(compact: CompactTree<T::Item>) -> Self {
+        assert_eq!(compact.begins.len(), compact.ends.len());
+
+        let (begin, end) = compact.range;
+        let mut tree = Self::new(T::from_bounds(begin, end));
+        for (begin, end) in compact.begins.into_iter().zip(compact.ends) {
+            tree.insert(T::from_bounds(begin, end));
+        }
+
+        tree
+    }
+
+    /// Picks an [`Interval`](trait.Interval.html) uniformly at random among those that contain
+    /// the `point`. Requires the `rand` feature.
+    ///
+    /// Reservoir sampling is used during the traversal, so the matches are not collected.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rand::{rngs::StdRng, SeedableRng};
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(85..95);
+    /// tree.insert(90..100);
+    ///
+    /// let mut rng = StdRng::seed_from_u64(42);
+    /// let sample = tree.sample_with_point(90, &mut rng).unwrap();
+    /// assert!(tree.find_with_point(90).contains(sample));
+    ///
+    /// assert_eq!(tree.sample_with_point(50, &mut rng), None);
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if the point is out-of-range of this interval tree.
+    #[cfg(feature = "rand")]
+    pub fn sample_with_point<R: rand::Rng>(&self, point: T::Item, rng: &mut R) -> Option<&T> {
+        assert!(!self.overflow_point(&point));
+
+        let mut count = 0;
+        let mut sample = None;
+        self.root.for_each_with_point_rec(point, 0, &mut |_, intv| {
+            count += 1;
+            if rng.gen_range(0..count) == 0 {
+                sample = Some(intv);
+            }
+        });
+
+        sample
+    }
+
+    /// Finds [`Interval`](trait.Interval.html)s in this interval tree that strictly cross the
+    /// `point`, i.e. that begin before and end after it.
+    ///
+    /// Unlike [`find_with_point`](#method.find_with_point), intervals beginning exactly at the
+    /// `point` are excluded.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(40..50);
+    /// tree.insert(50..60);
+    /// tree.insert(45..55);
+    ///
+    /// assert_eq!(tree.crossing(50), [&(45..55)].iter().cloned().collect());
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if the point is out-of-range of this interval tree.
+    pub fn crossing(&self, point: T::Item) -> HashSet<&T>
+    where
+        T: Eq + Hash,
+        T::Item: Clone,
+    {
+        let mut found = self.find_with_point(point.clone());
+        found.retain(|intv| intv.begin() < point);
+        found
+    }
+
+    /// Returns the centers of all nodes in this interval tree, in order, i.e. in ascending order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    /// assert_eq!(tree.centers(), vec![50]);
+    ///
+    /// tree.insert(20..30); // the node on 0..50 is created
+    /// tree.insert(60..90); // the node on 50..100 is created
+    /// tree.insert(5..10); // the nodes on 0..25 and 0..12 are created
+    ///
+    /// assert_eq!(tree.centers(), vec![6, 12, 25, 50, 75]);
+    /// ```
+    pub fn centers(&self) -> Vec<T::Item>
+    where
+        T::Item: Clone,
+    {
+        let mut centers = Vec::new();
+        self.root.centers_rec(&mut centers);
+        centers
+    }
+
+    /// Returns statistics of every node in this interval tree, in pre-order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree, NodeStat};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(45..55);
+    /// tree.insert(40..60);
+    /// tree.insert(20..30);
+    /// tree.insert(60..90);
+    ///
+    /// let stats = tree.node_stats();
+    /// assert_eq!(stats[0], NodeStat { depth: 0, center: 50, interval_count: 2 });
+    /// assert_eq!(stats[1], NodeStat { depth: 1, center: 25, interval_count: 1 });
+    /// assert_eq!(stats[2], NodeStat { depth: 1, center: 75, interval_count: 1 });
+    /// ```
+    pub fn node_stats(&self) -> Vec<NodeStat<T::Item>>
+    where
+        T::Item: Clone,
+    {
+        let mut stats = Vec::new();
+        self.root.node_stats_rec(0, &mut stats);
+        stats
+    }
+
+    /// Creates a [`WindowCursor`](struct.WindowCursor.html) that slides a window of `width` over
+    /// this interval tree.
+    ///
+    /// Empty intervals are never reported, as in
+    /// [`find_with_interval`](#method.find_with_interval).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(5..10);
+    /// tree.insert(85..95);
+    /// tree.insert(90..100);
+    ///
+    /// let mut cursor = tree.window_cursor(10);
+    /// assert_eq!(cursor.advance(0), [&(5..10)].iter().cloned().collect());
+    /// assert!(cursor.advance(10).is_empty());
+    /// assert_eq!(cursor.advance(80), [&(85..95)].iter().cloned().collect());
+    /// assert_eq!(cursor.advance(90), [&(85..95), &(90..100)].iter().cloned().collect());
+    /// ```
+    pub fn window_cursor(&self, width: T::Item) -> WindowCursor<'_, T> {
+        let mut pending: Vec<_> = self
+            .iter()
+            .filter(|intv| intv.begin() < intv.end())
+            .collect();
+        pending.sort_by(|a, b| b.begin().partial_cmp(&a.begin()).unwrap());
+
+        WindowCursor {
+            width,
+            begin: None,
+            pending,
+            active: Vec::new(),
+        }
+    }
+
+    /// Counts the [`Interval`](trait.Interval.html)s in this interval tree that lie entirely left
+    /// of the `point`, contain it, and lie entirely right of it, respectively.
+    ///
+    /// An interval lies left of the `point` if it ends at or before it, and right of the `point`
+    /// if it begins after it. Intervals beginning exactly at the `point` contain it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(10..20);
+    /// tree.insert(20..50);
+    /// tree.insert(40..60);
+    /// tree.insert(50..60);
+    /// tree.insert(70..80);
+    ///
+    /// assert_eq!(tree.partition_counts(50), (2, 2, 1));
+    /// ```
+    pub fn partition_counts(&self, point: T::Item) -> (usize, usize, usize) {
+        self.iter()
+            .fold((0, 0, 0), |(left, overlapping, right), intv| {
+                if intv.end() <= point {
+                    (left + 1, overlapping, right)
+                } else if intv.begin() > point {
+                    (left, overlapping, right + 1)
+                } else {
+                    (left, overlapping + 1, right)
+                }
+            })
+    }
+
+    /// Returns the [`Interval`](trait.Interval.html)s stored more than once in this interval
+    /// tree, each with the number of times it is stored, in ascending order of their bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(5..10);
+    /// tree.insert(85..95);
+    /// tree.insert(85..95);
+    /// tree.insert(5..10);
+    /// tree.insert(5..10);
+    /// tree.insert(40..60);
+    ///
+    /// assert_eq!(tree.duplicates(), vec![(&(5..10), 3), (&(85..95), 2)]);
+    /// ```
+    pub fn duplicates(&self) -> Vec<(&T, usize)>
+    where
+        T: Eq + Hash,
+    {
+        let mut counts = HashMap::new();
+        for intv in self.iter() {
+            *counts.entry(intv).or_insert(0) += 1;
+        }
+
+        let mut duplicates: Vec<_> = counts.into_iter().filter(|&(_, n)| n > 1).collect();
+        duplicates.sort_by(|(a, _), (b, _)| {
+            (a.begin(), a.end())
+                .partial_cmp(&(b.begin(), b.end()))
+                .unwrap()
+        });
+        duplicates
+    }
+
+    /// Creates a new interval tree on `window` holding the [`Interval`](trait.Interval.html)s of
+    /// this interval tree clipped to `window`.
+    ///
+    /// This is lossy: intervals not overlapping with `window` are dropped, and intervals
+    /// partially overlapping with it are cut at its bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..10);
+    /// for i in 0..=5 {
+    ///     tree.insert(i..(i + 5));
+    /// }
+    ///
+    /// let clipped = tree.clip(3..7);
+    /// assert_eq!(
+    ///     clipped.find_with_interval(3..7),
+    ///     [&(3..5), &(3..6), &(3..7), &(4..7), &(5..7)].iter().cloned().collect()
+    /// );
+    /// assert_eq!(
+    ///     clipped.find_with_point(6),
+    ///     [&(3..7), &(4..7), &(5..7)].iter().cloned().collect()
+    /// );
+    /// ```
+    pub fn clip(&self, window: T) -> IntervalTree<T> {
+        let mut tree = IntervalTree::new(window);
+        for intv in self.iter() {
+            tree.insert_clamped(intv.clone());
+        }
+
+        tree
+    }
+
+    /// Creates a interval tree on `range` holding clones of the
+    /// [`Interval`](trait.Interval.html)s in this interval tree for which `predicate` returns
+    /// `true`.
+    ///
+    /// Intervals not fitting in `range` are skipped. This interval tree is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(10..12);
+    /// tree.insert(10..40);
+    /// tree.insert(70..90);
+    ///
+    /// let filtered = tree.filter(0..50, |intv| intv.end - intv.start > 5);
+    /// assert_eq!(
+    ///     filtered.find_with_interval(0..50),
+    ///     [&(10..40)].iter().cloned().collect()
+    /// );
+    /// assert_eq!(tree.find_with_point(11).len(), 2);
+    /// ```
+    pub fn filter<F>(&self, range: T, predicate: F) -> IntervalTree<T>
+    where
+        F: Fn(&T) -> bool,
+    {
+        let mut tree = IntervalTree::new(range);
+        for intv in self.iter_by_insertion() {
+            if predicate(intv) && !tree.overflow_insertion(intv) {
+                tree.insert(intv.clone());
+            }
+        }
+
+        tree
+    }
+
+    /// Returns the number of [`Interval`](trait.Interval.html)s covering each point, run-length
+    /// encoded as `(begin, end, depth)` segments spanning the range of this interval tree.
+    ///
+    /// Adjacent segments always differ in depth. Uncovered segments have a depth of zero.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..20);
+    ///
+    /// tree.insert(2..8);
+    /// tree.insert(5..10);
+    /// tree.insert(10..12);
+    ///
+    /// assert_eq!(
+    ///     tree.depth_profile(),
+    ///     vec![(0, 2, 0), (2, 5, 1), (5, 8, 2), (8, 12, 1), (12, 20, 0)]
+    /// );
+    /// ```
+    pub fn depth_profile(&self) -> Vec<(T::Item, T::Item, usize)>
+    where
+        T::Item: Clone,
+    {
+        let mut profile: Vec<(T::Item, T::Item, usize)> = Vec::new();
+        let mut push = |begin: T::Item, end: T::Item, depth: usize| {
+            if begin >= end {
+                return;
+            }
+            match profile.last_mut() {
+                Some((_, last, d)) if *d == depth => *last = end,
+                _ => profile.push((begin, end, depth)),
+            }
+        };
+
+        let mut cursor = self.root.range.begin();
+        let mut depth = 0;
+        for (point, delta) in self.depth_changes() {
+            push(cursor, point.clone(), depth as usize);
+            cursor = point;
+            depth += delta;
+        }
+        push(cursor, self.root.range.end(), depth as usize);
+
+        profile
+    }
+
+    /// Returns the lengths of the alternating covered and uncovered runs spanning the range of
+    /// this interval tree, as `(covered, length)` pairs in ascending order.
+    ///
+    /// Unlike [`depth_profile`](#method.depth_profile), only whether a point is covered matters,
+    /// so adjacent runs always alternate.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..20);
+    /// assert_eq!(tree.coverage_rle(), vec![(false, 20)]);
+    ///
+    /// tree.insert(2..8);
+    /// tree.insert(5..10);
+    /// tree.insert(10..12);
+    /// assert_eq!(tree.coverage_rle(), vec![(false, 2), (true, 10), (false, 8)]);
+    /// ```
+    pub fn coverage_rle(&self) -> Vec<(bool, T::Item)> {
+        let mut runs = Vec::new();
+        let mut cursor = self.root.range.begin();
+
+        for (begin, end) in self.regions_over_threshold(1) {
+            let region = T::from_bounds(begin, end);
+            if cursor < region.begin() {
+                runs.push((false, T::from_bounds(cursor, region.begin()).length()));
+            }
+            runs.push((true, region.length()));
+            cursor = region.end();
+        }
+
+        let end = self.root.range.end();
+        if cursor < end {
+            runs.push((false, T::from_bounds(cursor, end).length()));
+        }
+
+        runs
+    }
+
+    /// Returns the average number of [`Interval`](trait.Interval.html)s covering a point, over
+    /// the range of this interval tree.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..10);
+    /// assert_eq!(tree.average_depth(), 0.0);
+    ///
+    /// tree.insert(0..10);
+    /// tree.insert(2..7);
+    /// assert_eq!(tree.average_depth(), 1.5);
+    /// ```
+    pub fn average_depth(&self) -> f64
+    where
+        T::Item: Clone + Sub<Output = T::Item>,
+        u128: TryFrom<T::Item>,
+    {
+        let to_f64 = |length: T::Item| u128::try_from(length).ok().unwrap() as f64;
+
+        let total = to_f64(self.root.range.length());
+        if total == 0.0 {
+            return 0.0;
+        }
+
+        let mut weighted = 0.0;
+        let mut depth = 0;
+        let mut prev: Option<T::Item> = None;
+        for (point, delta) in self.depth_changes() {
+            if let Some(prev) = prev {
+                weighted += depth as f64 * to_f64(point.clone() - prev);
+            }
+            depth += delta;
+            prev = Some(point);
+        }
+
+        weighted / total
+    }
+
+    /// Returns the smallest, largest and average numbers of [`Interval`](trait.Interval.html)s
+    /// covering a point, over the range of this interval tree, in a single sweep.
+    ///
+    /// All of them are zero if the range is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{DepthStats, Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..10);
+    ///
+    /// tree.insert(0..10);
+    /// tree.insert(2..7);
+    /// tree.insert(5..7);
+    ///
+    /// assert_eq!(
+    ///     tree.depth_stats(),
+    ///     DepthStats {
+    ///         min: 1,
+    ///         max: 3,
+    ///         mean: 1.7
+    ///     }
+    /// );
+    /// ```
+    pub fn depth_stats(&self) -> DepthStats
+    where
+        T::Item: Clone + Sub<Output = T::Item>,
+        u128: TryFrom<T::Item>,
+    {
+        let to_f64 = |length: T::Item| u128::try_from(length).ok().unwrap() as f64;
+
+        let total = to_f64(self.root.range.length());
+        if total == 0.0 {
+            return DepthStats {
+                min: 0,
+                max: 0,
+                mean: 0.0,
+            };
+        }
+
+        let (mut min, mut max) = (usize::MAX, 0);
+        let mut weighted = 0.0;
+        let mut depth = 0;
+        let mut cursor = self.root.range.begin();
+        let changes = self
+            .depth_changes()
+            .into_iter()
+            .map(|(point, delta)| (point, Some(delta)));
+        for (point, delta) in changes.chain(iter::once((self.root.range.end(), None))) {
+            if point > cursor {
+                min = min.min(depth as usize);
+                max = max.max(depth as usize);
+                weighted += depth as f64 * to_f64(point.clone() - cursor);
+                cursor = point;
+            }
+            depth += delta.unwrap_or(0);
+        }
+
+        DepthStats {
+            min,
+            max,
+            mean: weighted / total,
+        }
+    }
+
+    /// Returns the sub-ranges covered by this interval tree but not by `other`, in ascending
+    /// order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..10);
+    /// tree.insert(0..4);
+    /// tree.insert(3..10);
+    ///
+    /// let mut other = IntervalTree::new(0..10);
+    /// other.insert(3..6);
+    ///
+    /// assert_eq!(tree.difference_coverage(&other), vec![0..3, 6..10]);
+    /// ```
+    pub fn difference_coverage(&self, other: &IntervalTree<T>) -> Vec<T> {
+        self.combine_coverage(other, |this, other| this && !other)
+            .into_iter()
+            .map(|(begin, end)| T::from_bounds(begin, end))
+            .collect()
+    }
+
+    /// Returns the sub-ranges covered by exactly one of this interval tree and `other`, in
+    /// ascending order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..10);
+    /// tree.insert(0..6);
+    ///
+    /// let mut other = IntervalTree::new(0..10);
+    /// other.insert(3..9);
+    ///
+    /// assert_eq!(tree.symmetric_difference_coverage(&other), vec![0..3, 6..9]);
+    /// ```
+    pub fn symmetric_difference_coverage(&self, other: &IntervalTree<T>) -> Vec<T> {
+        self.combine_coverage(other, |this, other| this != other)
+            .into_iter()
+            .map(|(begin, end)| T::from_bounds(begin, end))
+            .collect()
+    }
+
+    /// Returns whether this interval tree and `other` cover the same points, regardless of the
+    /// [`Interval`](trait.Interval.html)s covering them.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    /// tree.insert(0..5);
+    /// tree.insert(5..10);
+    ///
+    /// let mut other = IntervalTree::new(0..100);
+    /// other.insert(0..10);
+    /// assert!(tree.covers_same_as(&other));
+    ///
+    /// other.insert(20..30);
+    /// assert!(!tree.covers_same_as(&other));
+    /// ```
+    pub fn covers_same_as(&self, other: &IntervalTree<T>) -> bool {
+        self.regions_over_threshold(1) == other.regions_over_threshold(1)
+    }
+
+    /// Returns whether every point covered by this interval tree is also covered by `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    /// tree.insert(2..5);
+    ///
+    /// let mut other = IntervalTree::new(0..100);
+    /// other.insert(0..10);
+    /// assert!(tree.coverage_subset_of(&other));
+    /// assert!(!other.coverage_subset_of(&tree));
+    /// ```
+    pub fn coverage_subset_of(&self, other: &IntervalTree<T>) -> bool {
+        self.combine_coverage(other, |this, other| this && !other)
+            .is_empty()
+    }
+
+    /// Returns whether this interval tree and `other` agree on whether each point is covered,
+    /// checking only the points of the range of this interval tree spaced by `step` from its
+    /// beginning.
+    ///
+    /// This looks up `len / step` points instead of comparing the coverages exactly, as
+    /// [`covers_same_as`](#method.covers_same_as) does. Differences falling between the sampled
+    /// points, or out of the range of this interval tree, go unnoticed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    /// tree.insert(0..50);
+    ///
+    /// let mut other = IntervalTree::new(0..100);
+    /// other.insert(0..48);
+    ///
+    /// assert!(tree.coverage_approx_eq(&other, 10));
+    /// assert!(!tree.coverage_approx_eq(&other, 1));
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if `step` is not positive.
+    pub fn coverage_approx_eq(&self, other: &IntervalTree<T>, step: T::Item) -> bool
+    where
+        T::Item: Clone + Add<Output = T::Item>,
+    {
+        let covered = |tree: &Self, point: &T::Item| {
+            !tree.overflow_point(point) && tree.overlaps_any_point(point.clone())
+        };
+
+        let begin = self.root.range.begin();
+        let zero = T::from_bounds(begin.clone(), begin.clone()).length();
+        assert!(step > zero);
+
+        let mut point = begin;
+        while point < self.root.range.end() {
+            if covered(self, &point) != covered(other, &point) {
+                return false;
+            }
+
+            // Stops before `point + step` may overflow past the end.
+            if T::from_bounds(point.clone(), self.root.range.end()).length() <= step {
+                break;
+            }
+            point = point + step.clone();
+        }
+
+        true
+    }
+
+    /// Returns the Jaccard similarity of the coverage of this interval tree and `other`, i.e.
+    /// the length covered by both divided by the length covered by either.
+    ///
+    /// The similarity of two trees covering nothing is defined as `1.0`, as their coverages are
+    /// equal.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..10);
+    /// let mut other = IntervalTree::new(0..10);
+    /// assert_eq!(tree.jaccard(&other), 1.0);
+    ///
+    /// tree.insert(0..6);
+    /// other.insert(2..8);
+    /// assert_eq!(tree.jaccard(&other), 0.5);
+    /// ```
+    pub fn jaccard(&self, other: &IntervalTree<T>) -> f64
+    where
+        T::Item: Clone + Sub<Output = T::Item>,
+        u128: TryFrom<T::Item>,
+    {
+        let covered_length = |regions: Vec<(T::Item, T::Item)>| -> f64 {
+            regions
+                .into_iter()
+                .map(|(begin, end)| u128::try_from(end - begin).ok().unwrap() as f64)
+                .sum()
+        };
+
+        let union = covered_length(self.combine_coverage(other, |this, other| this || other));
+        if union == 0.0 {
+            return 1.0;
+        }
+
+        covered_length(self.combine_coverage(other, |this, other| this && other)) / union
+    }
+
+    /// Returns the span of the covered regions overlapping `query`, or `None` if `query` overlaps
+    /// no [`Interval`](trait.Interval.html) in this interval tree.
+    ///
+    /// A covered region is a maximal run of points covered by chained overlapping or touching
+    /// intervals, so the span may reach far beyond `query`. If `query` overlaps several regions,
+    /// the span also covers the gaps between them.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(10..20);
+    /// tree.insert(15..30);
+    /// tree.insert(25..40);
+    /// tree.insert(60..70);
+    ///
+    /// assert_eq!(tree.covered_span_touching(12..14), Some(10..40));
+    /// assert_eq!(tree.covered_span_touching(35..65), Some(10..70));
+    /// assert_eq!(tree.covered_span_touching(45..55), None);
+    /// ```
+    pub fn covered_span_touching(&self, query: T) -> Option<T> {
+        let mut regions = self
+            .regions_over_threshold(1)
+            .into_iter()
+            .map(|(begin, end)| T::from_bounds(begin, end))
+            .filter(|region| region.overlaps(&query));
+
+        let first = regions.next()?;
+        let end = regions.last().map_or(first.end(), |last| last.end());
+        Some(T::from_bounds(first.begin(), end))
+    }
+
+    /// Returns whether the points of `window` covered by the
+    /// [`Interval`](trait.Interval.html)s in this interval tree form a single run.
+    ///
+    /// Unlike full coverage, the edges of `window` may be left uncovered. A `window` where nothing
+    /// is covered is not contiguous.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(10..20);
+    /// tree.insert(15..30);
+    /// tree.insert(40..50);
+    ///
+    /// assert!(tree.is_contiguous_over(0..35));
+    /// assert!(!tree.is_contiguous_over(0..45));
+    /// assert!(!tree.is_contiguous_over(32..38));
+    /// ```
+    pub fn is_contiguous_over(&self, window: T) -> bool {
+        self.regions_over_threshold(1)
+            .into_iter()
+            .map(|(begin, end)| T::from_bounds(begin, end))
+            .filter(|region| region.overlaps(&window))
+            .count()
+            == 1
+    }
+
+    /// Returns the sub-ranges of the range of this interval tree not covered by any
+    /// [`Interval`](trait.Interval.html), as `(begin, end)` pairs in ascending order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    /// assert_eq!(tree.gaps(), vec![(0, 100)]);
+    ///
+    /// tree.insert(10..20);
+    /// tree.insert(15..30);
+    /// tree.insert(50..100);
+    /// assert_eq!(tree.gaps(), vec![(0, 10), (30, 50)]);
+    /// ```
+    pub fn gaps(&self) -> Vec<(T::Item, T::Item)> {
+        let mut gaps = Vec::new();
+        let mut cursor = self.root.range.begin();
+
+        for (begin, end) in self.regions_over_threshold(1) {
+            if cursor < begin {
+                gaps.push((cursor, begin));
+            }
+            cursor = end;
+        }
+
+        let end = self.root.range.end();
+        if cursor < end {
+            gaps.push((cursor, end));
+        }
+
+        gaps
+    }
+
+    /// Returns an iterator over the [`gaps`](#method.gaps), computing each of them on demand.
+    ///
+    /// The [`Interval`](trait.Interval.html)s are sorted up front, but the gaps are not collected,
+    /// which pays off when only the first few of them are needed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(10..20);
+    /// tree.insert(15..30);
+    /// tree.insert(50..60);
+    ///
+    /// let mut gaps = tree.gaps_iter();
+    /// assert_eq!(gaps.next(), Some((0, 10)));
+    /// assert_eq!(gaps.next(), Some((30, 50)));
+    /// assert_eq!(gaps.next(), Some((60, 100)));
+    /// assert_eq!(gaps.next(), None);
+    /// ```
+    pub fn gaps_iter(&self) -> impl Iterator<Item = (T::Item, T::Item)> + '_ {
+        let mut sorted: Vec<_> = self
+            .iter()
+            .filter(|intv| intv.begin() < intv.end())
+            .collect();
+        sorted.sort_by(|a, b| a.begin().partial_cmp(&b.begin()).unwrap());
+        let mut intervals = sorted.into_iter();
+
+        // `None` once the last gap has been yielded.
+        let mut cursor = Some(self.root.range.begin());
+        iter::from_fn(move || {
+            let mut covered_until = cursor.take()?;
+            for intv in intervals.by_ref() {
+                if intv.begin() > covered_until {
+                    cursor = Some(intv.end());
+                    return Some((covered_until, intv.begin()));
+                }
+                if intv.end() > covered_until {
+                    covered_until = intv.end();
+                }
+            }
+
+            let end = self.root.range.end();
+            if covered_until < end {
+                Some((covered_until, end))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Counts the maximal runs of points covered by chained overlapping or touching
+    /// [`Interval`](trait.Interval.html)s in this interval tree.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    /// assert_eq!(tree.component_count(), 0);
+    ///
+    /// tree.insert(10..20);
+    /// tree.insert(15..30);
+    /// tree.insert(30..40);
+    /// tree.insert(50..60);
+    /// assert_eq!(tree.component_count(), 2);
+    /// ```
+    pub fn component_count(&self) -> usize {
+        let mut count = 0;
+        let mut depth = 0;
+        for (_, delta) in self.depth_changes() {
+            if depth == 0 && delta > 0 {
+                count += 1;
+            }
+            depth += delta;
+        }
+
+        count
+    }
+
+    /// Returns the first point at or after `from` in the range of this interval tree that no
+    /// [`Interval`](trait.Interval.html) covers, or `None` if the rest of the range is covered.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(10..20);
+    /// tree.insert(15..30);
+    /// tree.insert(90..100);
+    ///
+    /// assert_eq!(tree.next_uncovered(5), Some(5));
+    /// assert_eq!(tree.next_uncovered(12), Some(30));
+    /// assert_eq!(tree.next_uncovered(95), None);
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if the point is out-of-range of this interval tree.
+    pub fn next_uncovered(&self, from: T::Item) -> Option<T::Item> {
+        assert!(!self.overflow_point(&from));
+
+        // Covered regions are maximal, so their ends are not covered.
+        let point = match self
+            .regions_over_threshold(1)
+            .into_iter()
+            .find(|(begin, end)| *begin <= from && from < *end)
+        {
+            Some((_, end)) => end,
+            None => from,
+        };
+
+        if point < self.root.range.end() {
+            Some(point)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the first point at or after `from` covered by an
+    /// [`Interval`](trait.Interval.html) in this interval tree, or `None` if nothing is covered
+    /// from `from` onward.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(10..20);
+    /// tree.insert(50..60);
+    ///
+    /// assert_eq!(tree.next_covered(15), Some(15));
+    /// assert_eq!(tree.next_covered(20), Some(50));
+    /// assert_eq!(tree.next_covered(60), None);
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if the point is out-of-range of this interval tree.
+    pub fn next_covered(&self, from: T::Item) -> Option<T::Item> {
+        assert!(!self.overflow_point(&from));
+
+        self.regions_over_threshold(1)
+            .into_iter()
+            .find(|(_, end)| from < *end)
+            .map(|(begin, _)| if begin < from { from } else { begin })
+    }
+
+    /// Returns the [gap](#method.gaps) containing the `point` as a `(begin, end)` pair, or
+    /// `None` if the point is covered by an [`Interval`](trait.Interval.html).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(10..20);
+    /// tree.insert(50..60);
+    ///
+    /// assert_eq!(tree.gap_at(5), Some((0, 10)));
+    /// assert_eq!(tree.gap_at(20), Some((20, 50)));
+    /// assert_eq!(tree.gap_at(15), None);
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if the point is out-of-range of this interval tree.
+    pub fn gap_at(&self, point: T::Item) -> Option<(T::Item, T::Item)>
+    where
+        T::Item: Clone,
+    {
+        if self.overlaps_any_point(point.clone()) {
+            return None;
+        }
+
+        // Nothing covers the point, so the gap spans from the latest end at or before it to the
+        // earliest begin after it. Empty intervals bound no gap.
+        let mut begin = self.root.range.begin();
+        let mut end = self.root.range.end();
+        for intv in self.iter().filter(|intv| intv.begin() < intv.end()) {
+            if intv.end() <= point && intv.end() > begin {
+                begin = intv.end();
+            }
+            if intv.begin() > point && intv.begin() < end {
+                end = intv.begin();
+            }
+        }
+
+        Some((begin, end))
+    }
+
+    /// Returns the widest of the [`gaps`](#method.gaps), or `None` if the whole range is
+    /// covered.
+    ///
+    /// Ties are broken in favor of the leftmost gap.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    /// assert_eq!(tree.largest_gap(), Some((0, 100)));
+    ///
+    /// tree.insert(10..20);
+    /// tree.insert(50..100);
+    /// assert_eq!(tree.largest_gap(), Some((20, 50)));
+    ///
+    /// tree.insert(0..100);
+    /// assert_eq!(tree.largest_gap(), None);
+    /// ```
+    pub fn largest_gap(&self) -> Option<(T::Item, T::Item)>
+    where
+        T::Item: Clone + Sub<Output = T::Item>,
+    {
+        self.gaps().into_iter().fold(None, |largest, (begin, end)| {
+            let length = end.clone() - begin.clone();
+            match largest {
+                Some((b, e)) if length <= e.clone() - b.clone() => Some((b, e)),
+                _ => Some((begin, end)),
+            }
+        })
+    }
+
+    /// Merges the [`Interval`](trait.Interval.html)s in this interval tree into disjoint ones,
+    /// also joining intervals separated by a gap of at most `max_gap`, in ascending order.
+    ///
+    /// Overlapping and touching intervals are always merged, so `max_gap` of zero gives the
+    /// covered regions as they are.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    /// tree.insert(0..3);
+    /// tree.insert(5..8);
+    /// tree.insert(6..10);
+    /// tree.insert(20..30);
+    ///
+    /// assert_eq!(tree.merge_within(0), vec![0..3, 5..10, 20..30]);
+    /// assert_eq!(tree.merge_within(2), vec![0..10, 20..30]);
+    /// assert_eq!(tree.merge_within(10), vec![0..30]);
+    /// ```
+    pub fn merge_within(&self, max_gap: T::Item) -> Vec<T>
+    where
+        T::Item: Clone + Sub<Output = T::Item>,
+    {
+        let mut merged: Vec<(T::Item, T::Item)> = Vec::new();
+        for (begin, end) in self.regions_over_threshold(1) {
+            match merged.last_mut() {
+                Some(last) if begin.clone() - last.1.clone() <= max_gap => last.1 = end,
+                _ => merged.push((begin, end)),
+            }
+        }
+
+        merged
+            .into_iter()
+            .map(|(begin, end)| T::from_bounds(begin, end))
+            .collect()
+    }
+
+    /// Returns the `point` itself if it is covered by an [`Interval`](trait.Interval.html) in
+    /// this interval tree, and otherwise the nearest covered point, or `None` if nothing is
+    /// covered.
+    ///
+    /// When the nearest covered points on the left and on the right are equally distant, the
+    /// one on the left is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    /// assert_eq!(tree.snap_to_covered(50), None);
+    ///
+    /// tree.insert(10..20);
+    /// tree.insert(30..40);
+    ///
+    /// assert_eq!(tree.snap_to_covered(15), Some(15));
+    /// assert_eq!(tree.snap_to_covered(9), Some(10));
+    /// assert_eq!(tree.snap_to_covered(22), Some(19));
+    /// assert_eq!(tree.snap_to_covered(27), Some(30));
+    /// assert_eq!(tree.snap_to_covered(24), Some(19));
+    /// assert_eq!(tree.snap_to_covered(90), Some(39));
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if the point is out-of-range of this interval tree.
+    pub fn snap_to_covered(&self, point: T::Item) -> Option<T::Item>
+    where
+        T: DoubleEndedIterator,
+        T::Item: Clone + Sub<Output = T::Item>,
+    {
+        assert!(!self.overflow_point(&point));
+
+        let runs = self.regions_over_threshold(1);
+        let split = runs.partition_point(|(begin, _)| *begin <= point);
+
+        let left = match split.checked_sub(1).map(|i| &runs[i]) {
+            Some((_, end)) if point < *end => return Some(point),
+            Some((begin, end)) => T::from_bounds(begin.clone(), end.clone()).next_back(),
+            None => None,
+        };
+        let right = runs.get(split).map(|(begin, _)| begin.clone());
+
+        match (left, right) {
+            (Some(left), Some(right)) => {
+                if right.clone() - point.clone() < point - left.clone() {
+                    Some(right)
+                } else {
+                    Some(left)
+                }
+            }
+            (left, right) => left.or(right),
+        }
+    }
+
+    /// Removes the [`Interval`](trait.Interval.html)s that end at or before `cutoff` from this
+    /// interval tree, and returns them in unspecified order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(5..10);
+    /// tree.insert(40..60);
+    /// tree.insert(45..50);
+    /// tree.insert(85..95);
+    ///
+    /// let mut drained = tree.drain_before(50);
+    /// drained.sort_by_key(|intv| intv.start);
+    /// assert_eq!(drained, vec![5..10, 45..50]);
+    ///
+    /// assert!(tree.find_with_point(5).is_empty());
+    /// assert_eq!(tree.find_with_point(45), [&(40..60)].iter().cloned().collect());
+    /// ```
+    pub fn drain_before(&mut self, cutoff: T::Item) -> Vec<T> {
+        let mut drained = Vec::new();
+        self.root
+            .drain_where(&|intv| intv.end() <= cutoff, &mut drained);
+        self.len -= drained.len();
+        drained
+    }
+
+    /// Removes the [`Interval`](trait.Interval.html)s that contain the `point` from this interval
+    /// tree, and returns them in unspecified order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(5..10);
+    /// tree.insert(40..60);
+    /// tree.insert(45..50);
+    /// tree.insert(50..55);
+    ///
+    /// let mut removed = tree.remove_with_point(47);
+    /// removed.sort_by_key(|intv| intv.start);
+    /// assert_eq!(removed, vec![40..60, 45..50]);
+    ///
+    /// assert!(tree.find_with_point(47).is_empty());
+    /// assert_eq!(tree.find_with_point(50), [&(50..55)].iter().cloned().collect());
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if the point is out-of-range of this interval tree.
+    pub fn remove_with_point(&mut self, point: T::Item) -> Vec<T> {
+        assert!(!self.overflow_point(&point));
+
+        let mut removed = Vec::new();
+        self.root.drain_with_point_rec(&point, &mut removed);
+        self.len -= removed.len();
+        removed
+    }
+
+    /// Removes the [`Interval`](trait.Interval.html)s that overlap with `interval` from this
+    /// interval tree, and returns them in unspecified order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(5..10);
+    /// tree.insert(40..60);
+    /// tree.insert(60..70);
+    /// tree.insert(85..95);
+    ///
+    /// let mut removed = tree.remove_with_interval(8..60);
+    /// removed.sort_by_key(|intv| intv.start);
+    /// assert_eq!(removed, vec![5..10, 40..60]);
+    ///
+    /// assert_eq!(tree.find_with_interval(0..100).len(), 2);
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if the interval is out-of-range of this interval tree.
+    pub fn remove_with_interval(&mut self, interval: T) -> Vec<T> {
+        assert!(!self.overflow_interval(&interval));
+
+        let mut removed = Vec::new();
+        self.root.drain_with_interval_rec(&interval, &mut removed);
+        self.len -= removed.len();
+        removed
+    }
+
+    /// Creates a interval tree holding the [`Interval`](trait.Interval.html)s of both this
+    /// interval tree and `other`, on the smallest range containing both of their ranges.
+    ///
+    /// The intervals are kept as they are, without merging overlapping ones, and an interval in
+    /// both trees is stored twice. The result is a plain tree created by
+    /// [`new`](#method.new), with the intervals of this interval tree inserted before those of
+    /// `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..50);
+    /// tree.insert(10..30);
+    ///
+    /// let mut other = IntervalTree::new(20..100);
+    /// other.insert(25..90);
+    ///
+    /// let union = tree.union(&other);
+    /// assert_eq!(union.find_with_point(10), [&(10..30)].iter().cloned().collect());
+    /// assert_eq!(
+    ///     union.find_with_point(27),
+    ///     [&(10..30), &(25..90)].iter().cloned().collect()
+    /// );
+    /// ```
+    pub fn union(&self, other: &IntervalTree<T>) -> IntervalTree<T> {
+        let begin = if other.root.range.begin() < self.root.range.begin() {
+            other.root.range.begin()
+        } else {
+            self.root.range.begin()
+        };
+        let end = if other.root.range.end() > self.root.range.end() {
+            other.root.range.end()
+        } else {
+            self.root.range.end()
+        };
+
+        let mut tree = IntervalTree::new(T::from_bounds(begin, end));
+        for intv in self
+            .iter_by_insertion()
+            .into_iter()
+            .chain(other.iter_by_insertion())
+        {
+            tree.insert(intv.clone());
+        }
+
+        tree
+    }
+
+    /// Creates a interval tree on the range of this interval tree, holding the
+    /// [`Interval`](trait.Interval.html)s stored in both this interval tree and `other`.
+    ///
+    /// An interval stored more than once in both trees is kept as many times as it is in the
+    /// tree holding fewer copies. The result is a plain tree created by [`new`](#method.new).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    /// tree.insert(10..30);
+    /// tree.insert(40..60);
+    ///
+    /// let mut other = IntervalTree::new(0..100);
+    /// other.insert(40..60);
+    /// other.insert(45..60);
+    ///
+    /// let intersection = tree.intersection(&other);
+    /// assert_eq!(
+    ///     intersection.find_with_interval(0..100),
+    ///     [&(40..60)].iter().cloned().collect()
+    /// );
+    /// ```
+    pub fn intersection(&self, other: &IntervalTree<T>) -> IntervalTree<T> {
+        let mut tree = IntervalTree::new(self.root.range.clone());
+        for intv in self.iter_by_insertion() {
+            if tree.count_of(intv) < other.count_of(intv) {
+                tree.insert(intv.clone());
+            }
+        }
+
+        tree
+    }
+
+    /// Returns the height of this interval tree, i.e. the number of nodes on the longest path
+    /// from the root to a leaf.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    /// assert_eq!(tree.height(), 1);
+    ///
+    /// tree.insert(20..30); // stored in the node on 0..50
+    /// assert_eq!(tree.height(), 2);
+    /// ```
+    pub fn height(&self) -> usize {
+        self.root.height()
+    }
+
+    /// Returns whether every [`Interval`](trait.Interval.html) in this interval tree lies within
+    /// `constraint`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    /// tree.insert(20..30);
+    /// tree.insert(45..80);
+    ///
+    /// assert!(tree.all_within(&(20..80)));
+    /// assert!(!tree.all_within(&(20..70)));
+    /// ```
+    pub fn all_within(&self, constraint: &T) -> bool {
+        self.iter()
+            .all(|intv| intv.begin() >= constraint.begin() && intv.end() <= constraint.end())
+    }
+
+    /// Returns the fewest [`Interval`](trait.Interval.html)s in this interval tree whose union
+    /// covers `query`, in ascending order of their beginnings, or `None` if `query` cannot be
+    /// covered.
+    ///
+    /// The intervals are chosen greedily: among those beginning at or before the uncovered part
+    /// of `query`, the one reaching the furthest is taken.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(0..30);
+    /// tree.insert(10..50);
+    /// tree.insert(20..60);
+    /// tree.insert(55..70);
+    ///
+    /// assert_eq!(tree.minimal_cover(5..60), Some(vec![&(0..30), &(20..60)]));
+    /// assert_eq!(tree.minimal_cover(50..80), None);
+    /// ```
+    pub fn minimal_cover(&self, query: T) -> Option<Vec<&T>> {
+        let mut candidates: Vec<_> = self.iter().filter(|intv| intv.overlaps(&query)).collect();
+        candidates.sort_by(|a, b| a.begin().partial_cmp(&b.begin()).unwrap());
+        let mut candidates = candidates.into_iter().peekable();
+
+        let mut cover = Vec::new();
+        let mut cursor = query.begin();
+        while cursor < query.end() {
+            let mut furthest: Option<&T> = None;
+            while let Some(intv) = candidates.next_if(|intv| intv.begin() <= cursor) {
+                if furthest.is_none_or(|furthest| intv.end() > furthest.end()) {
+                    furthest = Some(intv);
+                }
+            }
+
+            match furthest {
+                Some(intv) if intv.end() > cursor => {
+                    cursor = intv.end();
+                    cover.push(intv);
+                }
+                _ => return None,
+            }
+        }
+
+        Some(cover)
+    }
+
+    /// Returns the [`Interval`](trait.Interval.html)s in this interval tree, each split at the
+    /// `cuts` falling strictly inside it, in ascending order of their beginnings.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(0..10);
+    /// tree.insert(20..30);
+    ///
+    /// assert_eq!(
+    ///     tree.split_at_points(&[25, 7, 3, 20, 7]),
+    ///     vec![0..3, 3..7, 7..10, 20..25, 25..30]
+    /// );
+    /// ```
+    pub fn split_at_points(&self, cuts: &[T::Item]) -> Vec<T>
+    where
+        T::Item: Clone,
+    {
+        let mut cuts = cuts.to_vec();
+        cuts.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        cuts.dedup();
+
+        let mut pieces = Vec::new();
+        for intv in self.iter() {
+            let mut begin = intv.begin();
+            let first = cuts.partition_point(|cut| *cut <= begin);
+            for cut in cuts[first..].iter().take_while(|&cut| *cut < intv.end()) {
+                pieces.push(T::from_bounds(begin, cut.clone()));
+                begin = cut.clone();
+            }
+            pieces.push(T::from_bounds(begin, intv.end()));
+        }
+
+        pieces.sort_by(|a, b| a.begin().partial_cmp(&b.begin()).unwrap());
+        pieces
+    }
+
+    /// Returns how many times an [`Interval`](trait.Interval.html) equal to `interval` is stored
+    /// in this interval tree.
+    ///
+    /// Only the node where `interval` would be inserted is searched.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(40..60);
+    /// tree.insert(40..60);
+    /// tree.insert(40..70);
+    ///
+    /// assert_eq!(tree.count_of(&(40..60)), 2);
+    /// assert_eq!(tree.count_of(&(40..70)), 1);
+    /// assert_eq!(tree.count_of(&(5..10)), 0);
+    /// ```
+    pub fn count_of(&self, interval: &T) -> usize {
+        self.root.count_of(interval)
+    }
+
+    /// Maps each [`Interval`](trait.Interval.html) in this interval tree to the other intervals
+    /// in this interval tree it overlaps with.
+    ///
+    /// Each interval is queried with [`find_with_interval`](#method.find_with_interval), so this
+    /// takes as many queries as there are intervals, and the map may hold as many entries as
+    /// there are overlapping pairs. Equal intervals share a single key and are not regarded as
+    /// overlapping each other.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(10..30);
+    /// tree.insert(20..40);
+    /// tree.insert(35..50);
+    ///
+    /// let adjacency = tree.overlap_adjacency();
+    /// assert_eq!(adjacency[&(10..30)], [&(20..40)].iter().cloned().collect());
+    /// assert_eq!(
+    ///     adjacency[&(20..40)],
+    ///     [&(10..30), &(35..50)].iter().cloned().collect()
+    /// );
+    /// ```
+    pub fn overlap_adjacency(&self) -> HashMap<&T, HashSet<&T>>
+    where
+        T: Eq + Hash,
+    {
+        self.iter()
+            .map(|intv| {
+                let mut overlapping = self.find_with_interval(intv.clone());
+                overlapping.remove(intv);
+                (intv, overlapping)
+            })
+            .collect()
+    }
+
+    /// Returns the [`Interval`](trait.Interval.html) in this interval tree overlapping the most
+    /// other intervals, or `None` if this interval tree is empty.
+    ///
+    /// Intervals equal to each other are counted once. Ties are broken in favor of the interval
+    /// inserted first.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    /// assert_eq!(tree.most_overlapping(), None);
+    ///
+    /// tree.insert(10..20);
+    /// tree.insert(15..40);
+    /// tree.insert(35..50);
+    ///
+    /// assert_eq!(tree.most_overlapping(), Some(&(15..40)));
+    /// ```
+    pub fn most_overlapping(&self) -> Option<&T>
+    where
+        T: Eq + Hash,
+    {
+        let mut most: Option<(&T, usize)> = None;
+        for intv in self.iter_by_insertion() {
+            // An interval overlaps itself unless it is empty.
+            let count = self
+                .find_with_interval(intv.clone())
+                .len()
+                .saturating_sub(1);
+            if most.is_none_or(|(_, max)| count > max) {
+                most = Some((intv, count));
+            }
+        }
+
+        most.map(|(intv, _)| intv)
+    }
+
+    /// Draws the [`Interval`](trait.Interval.html)s in this interval tree as a text timeline,
+    /// like the one in the crate documentation.
+    ///
+    /// The first line is an axis spanning the range of this interval tree over `width` columns.
+    /// Each of the following lines draws an interval from `*` at its beginning to `o` at its end,
+    /// in ascending order of beginnings. Endpoints are rounded to the nearest column, so short
+    /// intervals may be drawn as a lone `*` when the range is wider than `width`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..10);
+    /// tree.insert(3..8);
+    /// tree.insert(0..5);
+    ///
+    /// assert_eq!(
+    ///     tree.render_ascii(10),
+    ///     "|---------|\n\
+    ///      *----o\n\
+    ///      \x20  *----o\n"
+    /// );
+    /// ```
+    pub fn render_ascii(&self, width: usize) -> String
+    where
+        T::Item: Sub<Output = T::Item>,
+        u128: TryFrom<T::Item>,
+    {
+        let to_u128 = |x: T::Item| u128::try_from(x).ok().unwrap();
+        let length = to_u128(self.root.range.length());
+        let column = |point: T::Item| {
+            if length == 0 {
+                return 0;
+            }
+            let offset = to_u128(point - self.root.range.begin());
+            ((offset * width as u128 + length / 2) / length) as usize
+        };
+
+        let mut rendered = String::new();
+        rendered.push('|');
+        if width > 0 {
+            rendered.push_str(&"-".repeat(width - 1));
+            rendered.push('|');
+        }
+        rendered.push('\n');
+
+        let mut intervals: Vec<_> = self.iter().collect();
+        intervals.sort_by(|a, b| {
+            (a.begin(), a.end())
+                .partial_cmp(&(b.begin(), b.end()))
+                .unwrap()
+        });
+        for intv in intervals {
+            let (begin, end) = (column(intv.begin()), column(intv.end()));
+            rendered.push_str(&" ".repeat(begin));
+            rendered.push('*');
+            if end > begin {
+                rendered.push_str(&"-".repeat(end - begin - 1));
+                rendered.push('o');
+            }
+            rendered.push('\n');
+        }
+
+        rendered
+    }
+
+    /// Finds [`Interval`](trait.Interval.html)s in this interval tree whose lengths are at least
+    /// `min_len` and at most `max_len`.
+    ///
+    /// All the intervals are scanned, as the tree is not organized by length.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(0..2);
+    /// tree.insert(10..15);
+    /// tree.insert(40..60);
+    ///
+    /// assert_eq!(tree.find_by_length(3, 10), [&(10..15)].iter().cloned().collect());
+    /// ```
+    pub fn find_by_length(&self, min_len: T::Item, max_len: T::Item) -> HashSet<&T>
+    where
+        T: Eq + Hash,
+    {
+        self.iter()
+            .filter(|intv| {
+                let length = intv.length();
+                min_len <= length && length <= max_len
+            })
+            .collect()
+    }
+
+    /// Returns the points where the number of [`Interval`](trait.Interval.html)s covering a
+    /// point changes, in ascending order, each paired with the change.
+    ///
+    /// Every interval adds `+1` at its beginning and `-1` at its end. The changes at the same
+    /// point are summed up, and points where they cancel out are omitted.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(0..10);
+    /// tree.insert(5..10);
+    /// tree.insert(10..20);
+    ///
+    /// assert_eq!(tree.depth_events(), vec![(0, 1), (5, 1), (10, -1), (20, -1)]);
+    /// ```
+    pub fn depth_events(&self) -> Vec<(T::Item, i64)> {
+        self.depth_changes()
+            .into_iter()
+            .filter(|&(_, delta)| delta != 0)
+            .map(|(point, delta)| (point, delta as i64))
+            .collect()
+    }
+
+    /// Finds [`Interval`](trait.Interval.html)s in this interval tree that contain the `point`,
+    /// in the order they were inserted.
+    ///
+    /// [`rebuild_with_range`](#method.rebuild_with_range) keeps the order, while intervals
+    /// inserted by [`insert_many_sorted`](#method.insert_many_sorted) are ordered as given.
+    /// Intervals stored more than once are returned as many times.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(45..55);
+    /// tree.insert(10..60);
+    /// tree.insert(40..90);
+    ///
+    /// assert_eq!(
+    ///     tree.find_with_point_by_insertion(50),
+    ///     vec![&(45..55), &(10..60), &(40..90)]
+    /// );
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if the point is out-of-range of this interval tree.
+    pub fn find_with_point_by_insertion(&self, point: T::Item) -> Vec<&T> {
+        assert!(!self.overflow_point(&point));
+
+        let mut found = Vec::new();
+        self.root
+            .find_with_point_by_insertion_rec(point, &mut found);
+        found.sort_by_key(|intv| intv.sequence());
+        found.into_iter().map(|intv| &**intv).collect()
+    }
+
+    /// Estimates the number of bytes this interval tree occupies, including its nodes and the
+    /// allocated capacity of the heaps storing the [`Interval`](trait.Interval.html)s.
+    ///
+    /// This is only an estimate: padding added by the allocator and memory owned by the intervals
+    /// themselves are not counted.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    /// let empty = tree.estimated_memory();
+    ///
+    /// tree.insert(40..60);
+    /// assert!(tree.estimated_memory() > empty);
+    /// ```
+    pub fn estimated_memory(&self) -> usize {
+        let nodes: usize = self
+            .nodes()
+            .map(|node| {
+                mem::size_of::<Node<T>>()
+                    + node.overlaps_begin.capacity() * mem::size_of::<BeginSorted<T>>()
+                    + node.overlaps_end.capacity() * mem::size_of::<EndSorted<T>>()
+            })
+            .sum();
+
+        // The root node is embedded in this interval tree rather than allocated on its own.
+        mem::size_of::<Self>() - mem::size_of::<Node<T>>() + nodes
+    }
+
+    /// Finds [`Interval`](trait.Interval.html)s in this interval tree that contain the `point`
+    /// and lie in the layers from `min_layer` to `max_layer`, both inclusive, in layer order.
+    ///
+    /// Layers stack the matches in order of their beginnings, starting from layer zero for the
+    /// one that begins first. Ties are broken in favor of the interval that ends last, so that
+    /// an enclosing interval is layered under the ones it encloses.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(30..60);
+    /// tree.insert(40..60);
+    /// tree.insert(10..80);
+    ///
+    /// assert_eq!(tree.find_with_point_depth_band(50, 1, 5), vec![&(30..60), &(40..60)]);
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if the point is out-of-range of this interval tree.
+    pub fn find_with_point_depth_band(
+        &self,
+        point: T::Item,
+        min_layer: usize,
+        max_layer: usize,
+    ) -> Vec<&T> {
+        if max_layer < min_layer {
+            assert!(!self.overflow_point(&point));
+            return Vec::new();
+        }
+
+        let mut found: Vec<_> = self.find_with_point_iter(point).collect();
+        found.sort_by(|a, b| {
+            (a.begin(), b.end())
+                .partial_cmp(&(b.begin(), a.end()))
+                .unwrap()
+        });
+
+        found
+            .into_iter()
+            .skip(min_layer)
+            .take(max_layer - min_layer + 1)
+            .collect()
+    }
+
+    /// Returns the [`Interval`](trait.Interval.html)s in this interval tree that lie within
+    /// another, different interval in this interval tree, in ascending order of their
+    /// beginnings.
+    ///
+    /// Equal intervals do not shadow each other.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(10..50);
+    /// tree.insert(20..30);
+    /// tree.insert(40..60);
+    ///
+    /// assert_eq!(tree.shadowed(), vec![&(20..30)]);
+    /// ```
+    pub fn shadowed(&self) -> Vec<&T> {
+        let mut sorted: Vec<_> = self.iter().collect();
+        sorted.sort_by(|a, b| {
+            (a.begin(), b.end())
+                .partial_cmp(&(b.begin(), a.end()))
+                .unwrap()
+        });
+
+        // The largest end so far, and the beginning of the first interval reaching it. Intervals
+        // before the current one begin at or before it.
+        let mut reach: Option<(T::Item, T::Item)> = None;
+        let mut shadowed = Vec::new();
+        for intv in sorted {
+            match reach {
+                Some((ref end, ref begin))
+                    if *end > intv.end() || (*end == intv.end() && *begin < intv.begin()) =>
+                {
+                    shadowed.push(intv);
+                }
+                Some((ref end, _)) if *end >= intv.end() => {}
+                _ => reach = Some((intv.end(), intv.begin())),
+            }
+        }
+
+        shadowed
+    }
+
+    /// Assigns the [`Interval`](trait.Interval.html)s in this interval tree to the fewest lanes
+    /// such that no two intervals in a lane overlap.
+    ///
+    /// The intervals are taken in ascending order of their beginnings, each put in the lane that
+    /// became free the earliest, or in a new lane if none is free. Each lane is thus in
+    /// ascending order, and there are as many lanes as the maximum number of intervals
+    /// overlapping at a point.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(0..30);
+    /// tree.insert(20..50);
+    /// tree.insert(35..60);
+    ///
+    /// assert_eq!(
+    ///     tree.assign_lanes(),
+    ///     vec![vec![&(0..30), &(35..60)], vec![&(20..50)]]
+    /// );
+    /// ```
+    pub fn assign_lanes(&self) -> Vec<Vec<&T>> {
+        let mut sorted: Vec<_> = self.iter().collect();
+        sorted.sort_by(|a, b| a.begin().partial_cmp(&b.begin()).unwrap());
+
+        let mut lanes: Vec<Vec<&T>> = Vec::new();
+        for intv in sorted {
+            let free = lanes
+                .iter_mut()
+                .filter(|lane| lane.last().is_some_and(|last| last.end() <= intv.begin()))
+                .min_by(|a, b| {
+                    let (a, b) = (a.last().unwrap().end(), b.last().unwrap().end());
+                    a.partial_cmp(&b).unwrap()
+                });
+
+            match free {
+                Some(lane) => lane.push(intv),
+                None => lanes.push(vec![intv]),
+            }
+        }
+
+        lanes
+    }
+
+    /// Returns the maximal sets of [`Interval`](trait.Interval.html)s in this interval tree
+    /// overlapping each other, i.e. the maximal cliques of their overlap graph.
+    ///
+    /// Each clique is the set of intervals containing a point where the overlap depth reaches a
+    /// local maximum. The cliques are in ascending order of those points, and each of them is in
+    /// ascending order of beginnings. Empty intervals overlap nothing, so they are left out.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use interval_tree::{Interval, IntervalTree};
+    ///
+    /// let mut tree = IntervalTree::new(0..100);
+    ///
+    /// tree.insert(0..30);
+    /// tree.insert(20..50);
+    /// tree.insert(25..60);
+    ///
+    /// assert_eq!(
+    ///     tree.maximal_cliques(),
+    ///     vec![vec![&(0..30), &(20..50), &(25..60)]]
+    /// );
+    ///
+    /// tree.insert(55..70);
+    /// assert_eq!(
+    ///     tree.maximal_cliques(),
+    ///     vec![vec![&(0..30), &(20..50), &(25..60)], vec![&(25..60), &(55..70)]]
+    /// );
+    /// ```
+    pub fn maximal_cliques(&self) -> Vec<Vec<&T>> {
+        let mut sorted: Vec<_> = self
+            .iter()
+            .filter(|intv| intv.begin() < intv.end())
+            .collect();
+        sorted.sort_by(|a, b| a.begin().partial_cmp(&b.begin()).unwrap());
+
+        let mut cliques = Vec::new();
+        // The intervals containing the latest beginning, which is a local maximum of the depth
+        // if any of them ends before the next beginning.
+        let mut alive: Vec<&T> = Vec::new();
+        for intv in sorted {
+            if alive.iter().any(|a| a.end() <= intv.begin()) {
+                cliques.push(alive.clone());
+                alive.retain(|a| a.end() > intv.begin());
+            }
+            alive.push(intv);
+        }
+
+        if !alive.is_empty() {
+            cliques.push(alive);
+        }
+        cliques
+    }
+
+    fn nodes(&self) -> Nodes<'_, T> {
+        self.root.nodes()
+    }
+
+    fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        self.root.iter()
+    }
+
+    /// Returns the points where the overlap depth changes, in ascending order, each paired with
+    /// the net change of the depth at that point.
+    fn depth_changes(&self) -> Vec<(T::Item, isize)> {
+        let mut events = Vec::new();
+        for intv in self.iter() {
+            events.push((intv.begin(), 1));
+            events.push((intv.end(), -1));
+        }
+        events.sort_by(|(p, _), (q, _)| p.partial_cmp(q).unwrap());
+
+        let mut changes: Vec<(T::Item, isize)> = Vec::new();
+        for (point, delta) in events {
+            match changes.last_mut() {
+                Some((last, sum)) if *last == point => *sum += delta,
+                _ => changes.push((point, delta)),
+            }
+        }
+
+        changes
+    }
+
+    /// Returns the maximal sub-ranges where `f(covered by self, covered by other)` holds, in
+    /// ascending order. `f(false, false)` must be `false`.
+    fn combine_coverage<F>(&self, other: &Self, f: F) -> Vec<(T::Item, T::Item)>
+    where
+        F: Fn(bool, bool) -> bool,
+    {
+        let mut events: Vec<_> = self
+            .depth_changes()
+            .into_iter()
+            .map(|(point, delta)| (point, delta, 0))
+            .chain(
+                other
+                    .depth_changes()
+                    .into_iter()
+                    .map(|(point, delta)| (point, 0, delta)),
+            )
+            .collect();
+        events.sort_by(|(p, _, _), (q, _, _)| p.partial_cmp(q).unwrap());
+
+        let mut runs = Vec::new();
+        let (mut this, mut that) = (0, 0);
+        let mut start = None;
+
+        let mut events = events.into_iter().peekable();
+        while let Some((point, this_delta, that_delta)) = events.next() {
+            this += this_delta;
+            that += that_delta;
+            if events.peek().is_some_and(|(next, _, _)| *next == point) {
+                continue;
+            }
+
+            if f(this > 0, that > 0) {
+                if start.is_none() {
+                    start = Some(point);
+                }
+            } else if let Some(begin) = start.take() {
+                runs.push((begin, point));
+            }
+        }
+
+        runs
+    }
+
+    fn iter_by_insertion(&self) -> Vec<&T> {
+        let mut intervals: Vec<_> = self
+            .nodes()
+            .flat_map(|node| node.overlaps_begin.iter())
+            .collect();
+        intervals.sort_by_key(|intv| intv.sequence());
+        intervals.into_iter().map(|intv| &**intv).collect()
+    }
+
+    fn enforce_capacity_limit(&mut self) {
+        if let Some(limit) = self.capacity_limit {
+            while self.len > limit {
+                self.root.pop_earliest_ending();
+                self.len -= 1;
+            }
+        }
+    }
+
+    fn overflow_interval(&self, interval: &T) -> bool {
+        interval.begin() < self.root.range.begin() || interval.end() > self.root.range.end()
+    }
+
+    // An empty interval is stored at its position, which thus has to be a point of the range.
+    fn overflow_insertion(&self, interval: &T) -> bool {
+        self.overflow_interval(interval)
+            || (interval.begin() == interval.end() && self.overflow_point(&interval.begin()))
+    }
+
+    fn accepts(&self, interval: &T) -> bool {
+        (self.options.allow_empty || interval.begin() < interval.end())
+            && (self.options.allow_duplicates || self.count_of(interval) == 0)
+    }
+
+    fn overflow_point(&self, point: &T::Item) -> bool {
+        point < &self.root.range.begin() || point >= &self.root.range.end()
+    }
+}
+
+impl<T> Node<T>
+where
+    T: Interval,
+    <T as Iterator>::Item: PartialOrd,
+    BeginSorted<T>: Ord,
+    EndSorted<T>: Ord,
+{
+    fn new(range: T) -> Self {
+        let center = range.center();
+
+        Self {
+            range,
+            center,
+
+            left: None,
+            right: None,
+
+            overlaps_begin: BinaryHeap::new(),
+            overlaps_end: BinaryHeap::new(),
+        }
+    }
+
+    fn insert_rec(&mut self, interval: T, sequence: u64, compact: bool) {
+        match self.route(&interval) {
+            Ordering::Less => {
+                let range = self.range.left_half();
+                self.left
+                    .get_or_insert_with(|| Box::new(Node::new(range)))
+                    .insert_rec(interval, sequence, compact);
+            }
+            Ordering::Greater => {
+                let range = self.range.right_half();
+                self.right
+                    .get_or_insert_with(|| Box::new(Node::new(range)))
+                    .insert_rec(interval, sequence, compact);
+            }
+            Ordering::Equal => self.push_overlap(interval, sequence, compact),
+        }
+    }
+
+    fn insert_many_sorted_rec(&mut self, mut sorted: Vec<(u64, T)>, compact: bool) {
+        // Intervals beginning after the center, and only they, go to the right subtree.
+        let split = sorted.partition_point(|(_, intv)| intv.begin() <= self.center);
+        let right = sorted.split_off(split);
+
+        let mut left = Vec::new();
+        for (sequence, interval) in sorted {
+            match self.route(&interval) {
+                Ordering::Less => left.push((sequence, interval)),
+                Ordering::Greater => unreachable!(),
+                Ordering::Equal => self.push_overlap(interval, sequence, compact),
+            }
+        }
+
+        if !left.is_empty() {
+            let range = self.range.left_half();
+            self.left
+                .get_or_insert_with(|| Box::new(Node::new(range)))
+                .insert_many_sorted_rec(left, compact);
+        }
+        if !right.is_empty() {
+            let range = self.range.right_half();
+            self.right
+                .get_or_insert_with(|| Box::new(Node::new(range)))
+                .insert_many_sorted_rec(right, compact);
+        }
+    }
+
+    fn overlaps_any_point_rec(&self, point: T::Item) -> bool {
+        if point < self.center {
+            // The top of `overlaps_begin` begins the last.
+            self.overlaps_begin
+                .peek()
+                .is_some_and(|intv| intv.begin() <= point)
+                || self
+                    .left
+                    .as_ref()
+                    .is_some_and(|left| left.overlaps_any_point_rec(point))
+        } else {
+            self.overlaps_by_end().any(|intv| intv.end() > point)
+                || self
+                    .right
+                    .as_ref()
+                    .is_some_and(|right| right.overlaps_any_point_rec(point))
+        }
+    }
+
+    fn for_each_with_point_rec<'a, F>(&'a self, point: T::Item, depth: usize, f: &mut F)
+    where
+        F: FnMut(usize, &'a T),
+    {
+        if point < self.center {
+            for intv in self
+                .overlaps_begin
+                .iter()
+                .filter(|&intv| intv.begin() <= point)
+            {
+                f(depth, intv);
+            }
+
+            if let Some(ref left) = self.left {
+                left.for_each_with_point_rec(point, depth + 1, f);
+            }
+        } else {
+            for intv in self.overlaps_by_end().filter(|intv| intv.end() > point) {
+                f(depth, intv);
+            }
+
+            if let Some(ref right) = self.right {
+                right.for_each_with_point_rec(point, depth + 1, f);
+            }
+        }
+    }
+
+    fn point_events_rec<'a>(&'a self, point: T::Item, events: &mut PointEvents<&'a T>)
+    where
+        T: Eq + Hash,
+    {
+        for intv in self.overlaps_begin.iter() {
+            if intv.begin() == point {
+                events.beginning.insert(intv);
+            }
+            if intv.end() == point {
+                events.ending.insert(intv);
+            }
+            if intv.begin() < point && intv.end() > point {
+                events.ongoing.insert(intv);
+            }
+        }
+
+        if point <= self.center {
+            if let Some(ref left) = self.left {
+                left.point_events_rec(point, events);
+            }
+        } else if let Some(ref right) = self.right {
+            right.point_events_rec(point, events);
+        }
+    }
+
+    // Returns `false` as soon as a second interval is found.
+    fn uniquely_covered_rec<'a>(&'a self, point: T::Item, found: &mut Option<&'a T>) -> bool {
+        if point < self.center {
+            for intv in self
+                .overlaps_begin
+                .iter()
+                .filter(|intv| intv.begin() <= point)
+            {
+                if found.replace(intv).is_some() {
+                    return false;
+                }
+            }
+
+            self.left
+                .as_ref()
+                .is_none_or(|left| left.uniquely_covered_rec(point, found))
+        } else {
+            for intv in self.overlaps_by_end().filter(|intv| intv.end() > point) {
+                if found.replace(intv).is_some() {
+                    return false;
+                }
+            }
+
+            self.right
+                .as_ref()
+                .is_none_or(|right| right.uniquely_covered_rec(point, found))
+        }
+    }
+
+    fn find_ending_in_rec<'a>(&'a self, window: &T, found: &mut HashSet<&'a T>)
+    where
+        T: Eq + Hash,
+    {
+        for intv in self
+            .overlaps_by_end()
+            .filter(|intv| window.begin() <= intv.end() && intv.end() < window.end())
+        {
+            found.insert(intv);
+        }
+
+        // Intervals in the left subtree end at or before the center, and ones in the right
+        // subtree end after it.
+        if window.begin() <= self.center {
+            if let Some(ref left) = self.left {
+                left.find_ending_in_rec(window, found);
+            }
+        }
+        if window.end() > self.center {
+            if let Some(ref right) = self.right {
+                right.find_ending_in_rec(window, found);
+            }
+        }
+    }
+
+    fn find_beginning_in_rec<'a>(&'a self, window: &T, found: &mut HashSet<&'a T>)
+    where
+        T: Eq + Hash,
+    {
+        for intv in self
+            .overlaps_begin
+            .iter()
+            .filter(|intv| window.begin() <= intv.begin() && intv.begin() < window.end())
+        {
+            found.insert(intv);
+        }
+
+        // Intervals in the left subtree begin at or before the center, and ones in the right
+        // subtree begin after it.
+        if window.begin() <= self.center {
+            if let Some(ref left) = self.left {
+                left.find_beginning_in_rec(window, found);
+            }
+        }
+        if window.end() > self.center {
+            if let Some(ref right) = self.right {
+                right.find_beginning_in_rec(window, found);
+            }
+        }
+    }
+
+    fn check_invariants(&self, compact: bool) -> Result<(), String>
+    where
+        T: Eq + Hash,
+        T: Debug,
+        T::Item: Debug,
+    {
+        if let Some(intv) = self
+            .overlaps_begin
+            .iter()
+            .find(|intv| self.route(intv) != Ordering::Equal)
+        {
+            return Err(format!(
+                "{:?} does not straddle the center {:?}",
+                **intv, self.center
+            ));
+        }
+
+        if compact {
+            if let Some(intv) = self.overlaps_end.peek() {
+                return Err(format!(
+                    "{:?} is stored in the end heap of the compact node centered at {:?}",
+                    **intv, self.center
+                ));
+            }
+        } else {
+            let mut counts = HashMap::new();
+            for intv in self.overlaps_begin.iter() {
+                *counts.entry(&**intv).or_insert(0) += 1;
+            }
+            for intv in self.overlaps_end.iter() {
+                *counts.entry(&**intv).or_insert(0) -= 1;
+            }
+            if let Some((intv, _)) = counts.iter().find(|(_, &count)| count != 0) {
+                return Err(format!(
+                    "{:?} is not stored equally in both heaps of the node centered at {:?}",
+                    intv, self.center
+                ));
+            }
+        }
+
+        if let Some(ref left) = self.left {
+            if let Some(intv) = left.iter().find(|intv| intv.end() > self.center) {
+                return Err(format!(
+                    "{:?} in the left subtree ends after the center {:?}",
+                    intv, self.center
+                ));
+            }
+            left.check_invariants(compact)?;
+        }
+
+        if let Some(ref right) = self.right {
+            if let Some(intv) = right.iter().find(|intv| intv.begin() <= self.center) {
+                return Err(format!(
+                    "{:?} in the right subtree begins at or before the center {:?}",
+                    intv, self.center
+                ));
+            }
+            right.check_invariants(compact)?;
+        }
+
+        Ok(())
+    }
+
+    fn centers_rec(&self, centers: &mut Vec<T::Item>)
+    where
+        T::Item: Clone,
+    {
+        if let Some(ref left) = self.left {
+            left.centers_rec(centers);
+        }
+        centers.push(self.center.clone());
+        if let Some(ref right) = self.right {
+            right.centers_rec(centers);
+        }
+    }
+
+    fn node_stats_rec(&self, depth: usize, stats: &mut Vec<NodeStat<T::Item>>)
+    where
+        T::Item: Clone,
+    {
+        stats.push(NodeStat {
+            depth,
+            center: self.center.clone(),
+            interval_count: self.overlaps_begin.len(),
+        });
+
+        if let Some(ref left) = self.left {
+            left.node_stats_rec(depth + 1, stats);
+        }
+        if let Some(ref right) = self.right {
+            right.node_stats_rec(depth + 1, stats);
+        }
+    }
+
+    fn height(&self) -> usize {
+        let left = self.left.as_ref().map_or(0, |left| left.height());
+        let right = self.right.as_ref().map_or(0, |right| right.height());
+        1 + left.max(right)
+    }
+
+    fn count_of(&self, interval: &T) -> usize {
+        match self.route(interval) {
+            Ordering::Less => self.left.as_ref().map_or(0, |left| left.count_of(interval)),
+            Ordering::Greater => self
+                .right
+                .as_ref()
+                .map_or(0, |right| right.count_of(interval)),
+            Ordering::Equal => self
+                .overlaps_begin
+                .iter()
+                .filter(|intv| ***intv == *interval)
+                .count(),
+        }
+    }
+
+    fn find_with_point_by_insertion_rec<'a>(
+        &'a self,
+        point: T::Item,
+        found: &mut Vec<&'a BeginSorted<T>>,
+    ) {
+        // Sequence numbers are only kept in `overlaps_begin`.
+        found.extend(
+            self.overlaps_begin
+                .iter()
+                .filter(|intv| intv.begin() <= point && intv.end() > point),
+        );
+
+        if point < self.center {
+            if let Some(ref left) = self.left {
+                left.find_with_point_by_insertion_rec(point, found);
+            }
+        } else if let Some(ref right) = self.right {
+            right.find_with_point_by_insertion_rec(point, found);
+        }
+    }
+
+    fn nodes(&self) -> Nodes<'_, T> {
+        Nodes { stack: vec![self] }
+    }
+
+    fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        self.nodes()
+            .flat_map(|node| node.overlaps_begin.iter().map(|intv| &**intv))
+    }
+
+    fn drain_where<F>(&mut self, pred: &F, drained: &mut Vec<T>)
+    where
+        F: Fn(&T) -> bool,
+    {
+        self.drain_node_where(pred, drained);
+
+        if let Some(ref mut left) = self.left {
+            left.drain_where(pred, drained);
+        }
+        if let Some(ref mut right) = self.right {
+            right.drain_where(pred, drained);
+        }
+    }
+
+    // Intervals containing the point are only in the nodes on the path the point is searched
+    // along.
+    fn drain_with_point_rec(&mut self, point: &T::Item, drained: &mut Vec<T>) {
+        self.drain_node_where(&|intv| intv.contains_point(point), drained);
+
+        let child = if *point < self.center {
+            self.left.as_mut()
+        } else {
+            self.right.as_mut()
+        };
+        if let Some(child) = child {
+            child.drain_with_point_rec(point, drained);
+        }
+    }
+
+    fn drain_with_interval_rec(&mut self, interval: &T, drained: &mut Vec<T>) {
+        self.drain_node_where(&|intv| intv.overlaps(interval), drained);
+
+        // Intervals in the left subtree end at or before the center, and ones in the right
+        // subtree begin after it.
+        if interval.begin() < self.center {
+            if let Some(ref mut left) = self.left {
+                left.drain_with_interval_rec(interval, drained);
+            }
+        }
+        if interval.end() > self.center {
+            if let Some(ref mut right) = self.right {
+                right.drain_with_interval_rec(interval, drained);
+            }
+        }
+    }
+
+    // Drains from this node only, excluding its subtrees.
+    fn drain_node_where<F>(&mut self, pred: &F, drained: &mut Vec<T>)
+    where
+        F: Fn(&T) -> bool,
+    {
+        let (removed, kept): (Vec<_>, Vec<_>) = mem::take(&mut self.overlaps_begin)
+            .into_vec()
+            .into_iter()
+            .partition(|intv| pred(intv));
+
+        self.overlaps_begin = kept.into();
+        if !removed.is_empty() {
+            self.overlaps_end.retain(|intv| !pred(intv));
+            drained.extend(removed.into_iter().map(BeginSorted::into_inner));
+        }
+    }
+
+    // Intervals in the left subtree end before ones in this node. Ones in this node and the right
+    // subtree need to be compared.
+    fn pop_earliest_ending(&mut self) -> Option<T> {
+        if let Some(intv) = self
+            .left
+            .as_mut()
+            .and_then(|left| left.pop_earliest_ending())
+        {
+            return Some(intv);
+        }
+
+        let pop_right = match (self.earliest_end_here(), &self.right) {
+            (Some(top), Some(right)) => right.earliest_end().is_some_and(|end| end < top),
+            (Some(_), None) => false,
+            (None, _) => true,
+        };
+        if pop_right {
+            return self
+                .right
+                .as_mut()
+                .and_then(|right| right.pop_earliest_ending());
+        }
+
+        let end = self.earliest_end_here()?;
+        let mut begins = mem::take(&mut self.overlaps_begin).into_vec();
+        let intv = if self.overlaps_end.is_empty() {
+            let i = begins.iter().position(|begin| begin.end() == end).unwrap();
+            begins.swap_remove(i).into_inner()
+        } else {
+            let intv = self.overlaps_end.pop().unwrap().into_inner();
+            let i = begins.iter().position(|begin| **begin == intv).unwrap();
+            begins.swap_remove(i);
+            intv
+        };
+        self.overlaps_begin = begins.into();
+
+        Some(intv)
+    }
+
+    fn earliest_end(&self) -> Option<T::Item> {
+        if let Some(end) = self.left.as_ref().and_then(|left| left.earliest_end()) {
+            return Some(end);
+        }
+
+        let right = self.right.as_ref().and_then(|right| right.earliest_end());
+        match (self.earliest_end_here(), right) {
+            (Some(end), Some(right)) if right < end => Some(right),
+            (Some(end), _) => Some(end),
+            (None, right) => right,
+        }
+    }
+
+    // Earliest end among the intervals stored in this node, excluding its subtrees.
+    fn earliest_end_here(&self) -> Option<T::Item> {
+        if self.overlaps_end.is_empty() {
+            self.overlaps_begin
+                .iter()
+                .map(|intv| intv.end())
+                .min_by(|a, b| a.partial_cmp(b).unwrap())
+        } else {
+            self.overlaps_end.peek().map(|top| top.end())
+        }
+    }
+
+    fn push_overlap(&mut self, interval: T, sequence: u64, compact: bool) {
+        if !compact {
+            self.overlaps_end.push(interval.to_end_sorted());
+        }
+        self.overlaps_begin
+            .push(interval.to_begin_sorted().with_sequence(sequence));
+    }
+
+    // Intervals stored in this node, for scans by their ends. A compact node keeps them only in
+    // `overlaps_begin`, leaving `overlaps_end` empty.
+    fn overlaps_by_end(&self) -> impl Iterator<Item = &T> {
+        let compact = self.overlaps_end.is_empty();
+        self.overlaps_end.iter().map(|intv| &**intv).chain(
+            self.overlaps_begin
+                .iter()
+                .filter(move |_| compact)
+                .map(|intv| &**intv),
+        )
+    }
+
+    // Where `interval` is stored: `Less` for the left subtree, `Greater` for the right subtree and
+    // `Equal` for this node. An empty interval at the center stays in this node, or it would be
+    // routed to the left forever.
+    fn route(&self, interval: &T) -> Ordering {
+        if interval.begin() == self.center && interval.end() == self.center {
+            Ordering::Equal
+        } else if interval.end() <= self.center {
+            Ordering::Less
+        } else if interval.begin() > self.center {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    }
+}
+
+macro_rules! impl_interval_tree_for_range {
+    ($int:ty) => {
+        impl IntervalTree<Range<$int>> {
+            /// Creates a interval tree on `range` holding intervals built from `(begin, end)`
+            /// tuples.
+            ///
+            /// As this is defined for each integer type, the type of the tree needs to be
+            /// spelled out, as in `IntervalTree::<Range<i32>>::from_tuples(0..100, &[(5, 10)])`.
+            ///
+            /// # Panic
+            ///
+            /// Panics if a tuple is reversed, i.e. `begin > end`, or if an interval overflows
+            /// `range`.
+            pub fn from_tuples(range: Range<$int>, tuples: &[($int, $int)]) -> Self {
+                let mut tree = Self::new(range);
+                for &(begin, end) in tuples {
+                    assert!(begin <= end);
+                    tree.insert(begin..end);
+                }
+
+                tree
+            }
+
+            /// Creates a interval tree on `range` holding intervals built from `(begin, end)`
+            /// pairs of strings, such as the fields of CSV records.
+            ///
+            /// Each endpoint is parsed by `str::parse` after trimming surrounding whitespace.
+            /// The first pair with an endpoint failing to parse, with its beginning greater than
+            /// its end, or overflowing `range` is reported along with its position.
+            ///
+            /// # Examples
+            ///
+            /// ```rust
+            /// use std::ops::Range;
+            /// use interval_tree::{Interval, IntervalTree, ParseError};
+            ///
+            /// let tree = IntervalTree::<Range<u32>>::try_from_str_pairs(
+            ///     0..100,
+            ///     vec![("5", "10"), (" 40", "60 ")],
+            /// )
+            /// .unwrap();
+            /// assert_eq!(tree.find_with_point(50), [&(40..60)].iter().cloned().collect());
+            ///
+            /// let pairs = vec![("5", "10"), ("x", "60")];
+            /// let err = IntervalTree::<Range<u32>>::try_from_str_pairs(0..100, pairs).unwrap_err();
+            /// assert_eq!(err.line(), 1);
+            ///
+            /// let pairs = vec![("5", "10"), ("60", "40")];
+            /// let err = IntervalTree::<Range<u32>>::try_from_str_pairs(0..100, pairs).unwrap_err();
+            /// assert_eq!(err, ParseError::Reversed { line: 1 });
+            /// ```
+            pub fn try_from_str_pairs<I, S>(
+                range: Range<$int>,
+                pairs: I,
+            ) -> Result<Self, ParseError>
+            where
+                I: IntoIterator<Item = (S, S)>,
+                S: AsRef<str>,
+            {
+                let mut tree = Self::new(range);
+                for (line, (begin, end)) in pairs.into_iter().enumerate() {
+                    let parse = |field, s: S| {
+                        s.as_ref().trim().parse::<$int>().map_err(|source| {
+                            ParseError::InvalidEndpoint {
+                                line,
+                                field,
+                                source,
+                            }
+                        })
+                    };
+
+                    let begin = parse(0, begin)?;
+                    let end = parse(1, end)?;
+                    if begin > end {
+                        return Err(ParseError::Reversed { line });
+                    }
+                    if tree.overflow_insertion(&(begin..end)) {
+                        return Err(ParseError::OutOfRange { line });
+                    }
+                    tree.insert(begin..end);
+                }
+
+                Ok(tree)
+            }
+
+            /// Creates a interval tree holding `intervals` on the smallest range containing all of
+            /// them, widened by `padding` on each side, or returns `None` if `intervals` is empty.
+            ///
+            /// Later insertions reaching into the padding fit into the range as is, so that the
+            /// tree need not be rebuilt with [`rebuild_with_range`](#method.rebuild_with_range).
+            /// The range saturates at the bounds of the integer type.
+            ///
+            /// An empty interval, such as `5..5`, is contained in a range only if its point is, so
+            /// the range extends at least one past the point of every empty interval.
+            ///
+            /// # Panic
+            ///
+            /// Panics if an empty interval lies at the largest value of the integer type, which no
+            /// range contains.
+            pub fn from_intervals_padded<I>(intervals: I, padding: $int) -> Option<Self>
+            where
+                I: IntoIterator<Item = Range<$int>>,
+            {
+                let mut intervals: Vec<_> = intervals.into_iter().collect();
+                let begin = intervals.iter().map(|intv| intv.start).min()?;
+                let end = intervals
+                    .iter()
+                    .map(|intv| intv.end.max(intv.start.saturating_add(1)))
+                    .max()?;
+                intervals.sort_by_key(|intv| intv.start);
+
+                let range = begin.saturating_sub(padding)..end.saturating_add(padding);
+                let mut tree = Self::new(range);
+                tree.insert_many_sorted(intervals);
+                Some(tree)
+            }
+        }
+    };
+}
+
+impl_interval_tree_for_range!(u8);
+impl_interval_tree_for_range!(i8);
+impl_interval_tree_for_range!(u16);
+impl_interval_tree_for_range!(i16);
+impl_interval_tree_for_range!(u32);
+impl_interval_tree_for_range!(i32);
+impl_interval_tree_for_range!(u64);
+impl_interval_tree_for_range!(i64);
+impl_interval_tree_for_range!(u128);
+impl_interval_tree_for_range!(i128);
+impl_interval_tree_for_range!(usize);
+impl_interval_tree_for_range!(isize);
+
+/// Cursor sliding a window of fixed width forward over an
+/// [`IntervalTree`](struct.IntervalTree.html).
+///
+/// Created by [`IntervalTree::window_cursor`](struct.IntervalTree.html#method.window_cursor).
+/// Intervals enter and leave the set of overlaps incrementally as the window advances, instead of
+/// being searched from scratch at every position.
+pub struct WindowCursor<'a, T: Interval> {
+    width: T::Item,
+    begin: Option<T::Item>,
+
+    // Intervals not yet entered, sorted by descending begin.
+    pending: Vec<&'a T>,
+    active: Vec<&'a T>,
+}
+
+impl<'a, T> WindowCursor<'a, T>
+where
+    T: Interval + Eq + Hash,
+    T::Item: Clone + PartialOrd + Add<Output = T::Item>,
+{
+    /// Moves the window to begin at `begin`, and returns the
+    /// [`Interval`](trait.Interval.html)s overlapping with it.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the window moves backward.
+    pub fn advance(&mut self, begin: T::Item) -> HashSet<&'a T> {
+        if let Some(ref prev) = self.begin {
+            assert!(*prev <= begin);
+        }
+
+        let end = begin.clone() + self.width.clone();
+
+        self.active.retain(|intv| intv.end() > begin);
+        while let Some(&intv) = self.pending.last() {
+            if intv.begin() >= end {
+                break;
+            }
+
+            self.pending.pop();
+            if intv.end() > begin {
+                self.active.push(intv);
+            }
+        }
+
+        let found = if begin < end {
+            self.active.iter().cloned().collect()
+        } else {
+            HashSet::new()
+        };
+
+        self.begin = Some(begin);
+        found
+    }
+}
+
+/// Pre-order iterator over the nodes of an interval tree.
+struct Nodes<'a, T>
+where
+    T: Interval,
+    BeginSorted<T>: Ord,
+    EndSorted<T>: Ord,
+{
+    stack: Vec<&'a Node<T>>,
+}
+
+impl<'a, T> Iterator for Nodes<'a, T>
+where
+    T: Interval,
+    BeginSorted<T>: Ord,
+    EndSorted<T>: Ord,
+{
+    type Item = &'a Node<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+
+        if let Some(ref right) = node.right {
+            self.stack.push(right);
+        }
+        if let Some(ref left) = node.left {
+            self.stack.push(left);
+        }
+
+        Some(node)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    // Short intervals of lengths 1 to 7 beginning at each of 0..90, overlapping each other.
+    pub(crate) fn sample_intervals() -> Vec<Range<i32>> {
+        (0..90).map(|i| i..(i + (i % 7) + 1)).collect()
+    }
+
+    fn sample_tree() -> IntervalTree<Range<i32>> {
+        let mut tree = IntervalTree::new(0..100);
+        for intv in sample_intervals() {
+            tree.insert(intv);
+        }
+        tree
+    }
+
+    #[test]
+    #[should_panic]
+    fn panic_insert_begin() {
+        let mut tree = IntervalTree::new(1..11);
+        tree.insert(0..10);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panic_insert_end() {
+        let mut tree = IntervalTree::new(0..10);
+        tree.insert(1..11);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panic_find_with_point_begin() {
+        let tree = IntervalTree::new(1..11);
+        tree.find_with_point(0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panic_find_with_point_end() {
+        let tree = IntervalTree::new(0..10);
+        tree.find_with_point(10);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panic_find_with_interval_start() {
+        let tree = IntervalTree::new(1..11);
+        tree.find_with_interval(0..10);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panic_find_with_interval_end() {
+        let tree = IntervalTree::new(0..10);
+        tree.find_with_interval(1..11);
+    }
+
+    #[test]
+    fn check_invariants_after_inserts() {
+        let tree = sample_tree();
+        assert_eq!(tree.check_invariants(), Ok(()));
+    }
+
+    #[test]
+    fn check_invariants_detects_not_straddling() {
+        let mut tree = IntervalTree::new(0..100);
+        tree.insert(45..55);
+
+        tree.root.overlaps_begin.push((10..20).to_begin_sorted());
+        tree.root.overlaps_end.push((10..20).to_end_sorted());
+        assert!(tree.check_invariants().is_err());
+    }
+
+    #[test]
+    fn check_invariants_detects_heap_mismatch() {
+        let mut tree = IntervalTree::new(0..100);
+        tree.insert(45..55);
+
+        tree.root.overlaps_begin.push((40..60).to_begin_sorted());
+        assert!(tree.check_invariants().is_err());
+    }
+
+    #[test]
+    fn check_invariants_detects_misplaced_subtree_interval() {
+        let mut tree = IntervalTree::new(0..100);
+        tree.insert(5..10);
+        tree.insert(85..95);
+
+        let left = tree.root.left.as_mut().unwrap();
+        left.overlaps_begin.push((20..60).to_begin_sorted());
+        left.overlaps_end.push((20..60).to_end_sorted());
+        assert!(tree.check_invariants().is_err());
+
+        let mut tree = IntervalTree::new(0..100);
+        tree.insert(85..95);
+
+        let right = tree.root.right.as_mut().unwrap();
+        right.overlaps_begin.push((50..80).to_begin_sorted());
+        right.overlaps_end.push((50..80).to_end_sorted());
+        assert!(tree.check_invariants().is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn par_find_with_points_matches_sequential() {
+        let tree = sample_tree();
+
+        let points: Vec<_> = (0..100).collect();
+        let sequential: Vec<_> = points.iter().map(|&p| tree.find_with_point(p)).collect();
+        assert_eq!(tree.par_find_with_points(&points), sequential);
+    }
+
+    #[test]
+    fn insert_clamped() {
+        let mut tree = IntervalTree::new(10..20);
+        tree.insert_clamped(5..15);
+        tree.insert_clamped(18..30);
+        tree.insert_clamped(0..10);
+        tree.insert_clamped(20..30);
+
+        assert_eq!(tree.iter().count(), 2);
+        assert_eq!(
+            tree.find_with_point(10),
+            [&(10..15)].iter().cloned().collect()
+        );
+        assert_eq!(
+            tree.find_with_point(19),
+            [&(18..20)].iter().cloned().collect()
+        );
+    }
+
+    #[test]
+    fn innermost_at_breaks_ties_by_begin() {
+        let mut tree = IntervalTree::new(0..100);
+        tree.insert(45..55);
+        tree.insert(40..50);
+        tree.insert(48..58);
+
+        assert_eq!(tree.innermost_at(49), Some(&(40..50)));
+        assert_eq!(tree.innermost_at(99), None);
+    }
+
+    #[test]
+    fn outermost_at_breaks_ties_by_begin() {
+        let mut tree = IntervalTree::new(0..100);
+        tree.insert(45..55);
+        tree.insert(40..50);
+        tree.insert(48..58);
+
+        assert_eq!(tree.outermost_at(49), Some(&(40..50)));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn compact_round_trip() {
+        let tree = sample_tree();
+
+        let compact = serde_json::to_string(&tree.to_compact()).unwrap();
+        let verbose = serde_json::to_string(&tree.iter().collect::<Vec<_>>()).unwrap();
+        assert!(compact.len() < verbose.len());
+
+        let restored = IntervalTree::from_compact(serde_json::from_str(&compact).unwrap());
+        assert_eq!(restored.check_invariants(), Ok(()));
+        for p in 0..100 {
+            assert_eq!(restored.find_with_point(p), tree.find_with_point(p));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn sample_with_point_is_deterministic_with_seed() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let mut tree = IntervalTree::new(0..100);
+        for i in 0..10 {
+            tree.insert((40 + i)..(60 + i));
+        }
+        let matches = tree.find_with_point(55);
+
+        let mut rng = StdRng::seed_from_u64(0);
+        let samples: Vec<_> = (0..20)
+            .map(|_| tree.sample_with_point(55, &mut rng).unwrap())
+            .collect();
+        assert!(samples.iter().all(|sample| matches.contains(sample)));
+
+        let mut rng = StdRng::seed_from_u64(0);
+        let again: Vec<_> = (0..20)
+            .map(|_| tree.sample_with_point(55, &mut rng).unwrap())
+            .collect();
+        assert_eq!(samples, again);
+    }
+
+    #[test]
+    fn window_cursor_matches_find_with_interval() {
+        let mut tree = IntervalTree::new(0..10);
+        for i in 0..=5 {
+            tree.insert(i..(i + 5));
+        }
+
+        for width in 0..=4 {
+            let mut cursor = tree.window_cursor(width);
+            for begin in 0..=(10 - width) {
+                assert_eq!(
+                    cursor.advance(begin),
+                    tree.find_with_interval(begin..(begin + width))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn window_cursor_skips_empty_intervals() {
+        let mut tree = IntervalTree::new(0..10);
+        tree.insert(5..5);
+        tree.insert(4..6);
+
+        let mut cursor = tree.window_cursor(4);
+        assert_eq!(cursor.advance(3), tree.find_with_interval(3..7));
+        assert_eq!(cursor.advance(3), [&(4..6)].iter().cloned().collect());
+    }
+
+    #[test]
+    #[should_panic]
+    fn panic_window_cursor_backward() {
+        let tree = IntervalTree::new(0..10);
+        let mut cursor = tree.window_cursor(3);
+        cursor.advance(5);
+        cursor.advance(4);
+    }
+
+    #[test]
+    fn insert_many_sorted_matches_insert() {
+        let mut intervals = sample_intervals();
+        intervals.sort_by_key(|intv| intv.start);
+
+        let mut naive = IntervalTree::new(0..100);
+        let mut batched = IntervalTree::new(0..100);
+        for intv in &intervals[..30] {
+            naive.insert(intv.clone());
+            batched.insert(intv.clone());
+        }
+        for intv in &intervals[30..] {
+            naive.insert(intv.clone());
+        }
+        batched.insert_many_sorted(intervals[30..].to_vec());
+
+        assert_eq!(batched.check_invariants(), Ok(()));
+        assert_eq!(batched.height(), naive.height());
+        for p in 0..100 {
+            assert_eq!(batched.find_with_point(p), naive.find_with_point(p));
+        }
+    }
+
+    #[test]
+    fn insert_many_sorted_empty_at_centers() {
+        let sorted = vec![25..25, 25..50, 50..50, 50..60, 51..51, 75..75];
+
+        let mut naive = IntervalTree::new(0..100);
+        for intv in sorted.iter() {
+            naive.insert(intv.clone());
+        }
+        let mut batched = IntervalTree::new(0..100);
+        batched.insert_many_sorted(sorted);
+
+        assert_eq!(batched.check_invariants(), Ok(()));
+        assert_eq!(
+            batched.iter_with_centers().collect::<HashSet<_>>(),
+            naive.iter_with_centers().collect::<HashSet<_>>()
+        );
+    }
+
+    #[test]
+    fn insert_open_across_boundary() {
+        let mut tree = IntervalTree::new(0..100);
+        tree.insert_open(..50);
+        tree.insert_open(50..);
+        tree.insert_open(..);
+        tree.insert_open(20..30);
+
+        assert_eq!(tree.check_invariants(), Ok(()));
+        assert_eq!(
+            tree.find_with_point(49),
+            [&(0..50), &(0..100)].iter().cloned().collect()
+        );
+        assert_eq!(
+            tree.find_with_point(50),
+            [&(50..100), &(0..100)].iter().cloned().collect()
+        );
+        assert_eq!(
+            tree.find_with_interval(45..55),
+            [&(0..50), &(50..100), &(0..100)].iter().cloned().collect()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn panic_insert_open_end() {
+        let mut tree = IntervalTree::new(0..100);
+        tree.insert_open(..150);
+    }
+
+    #[test]
+    fn drain_before_keeps_heaps_consistent() {
+        let mut tree = sample_tree();
+
+        let drained = tree.drain_before(50);
+        assert!(drained.iter().all(|intv| intv.end <= 50));
+        assert!(tree.iter().all(|intv| intv.end > 50));
+        assert_eq!(drained.len() + tree.iter().count(), 90);
+        assert_eq!(tree.check_invariants(), Ok(()));
+    }
+
+    #[test]
+    fn point_events_match_brute_force() {
+        let tree = sample_tree();
+
+        for p in 0..100 {
+            let events = tree.point_events(p);
+            let all: Vec<_> = tree.iter().collect();
+            let filter = |f: &dyn Fn(&&Range<i32>) -> bool| -> HashSet<_> {
+                all.iter().cloned().filter(|intv| f(intv)).collect()
+            };
+
+            assert_eq!(events.beginning, filter(&|intv| intv.start == p));
+            assert_eq!(events.ending, filter(&|intv| intv.end == p));
+            assert_eq!(
+                events.ongoing,
+                filter(&|intv| intv.start < p && p < intv.end)
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn panic_rebuild_with_range() {
+        let mut tree = IntervalTree::new(0..100);
+        tree.insert(85..95);
+        tree.rebuild_with_range(0..90);
+    }
+
+    #[test]
+    fn symmetric_difference_coverage_touching() {
+        let mut tree = IntervalTree::new(0..20);
+        tree.insert(0..5);
+        tree.insert(10..15);
+
+        let mut other = IntervalTree::new(0..20);
+        other.insert(5..10);
+        other.insert(12..20);
+
+        assert_eq!(
+            tree.symmetric_difference_coverage(&other),
+            vec![0..12, 15..20]
+        );
+        assert_eq!(tree.symmetric_difference_coverage(&tree), vec![]);
+    }
+
+    #[test]
+    fn uniquely_covered_counts_duplicates() {
+        let mut tree = IntervalTree::new(0..100);
+        tree.insert(40..60);
+        assert_eq!(tree.uniquely_covered(50), Some(&(40..60)));
+
+        tree.insert(40..60);
+        assert_eq!(tree.uniquely_covered(50), None);
+    }
+
+    #[test]
+    fn fold_with_point_matches_manual_sum() {
+        let tree = sample_tree();
+
+        for p in 0..100 {
+            let manual: i32 = tree.find_with_point_iter(p).map(|intv| intv.length()).sum();
+            assert_eq!(
+                tree.fold_with_point(p, 0, |sum, intv| sum + intv.length()),
+                manual
+            );
+        }
+    }
+
+    #[test]
+    fn find_endpoints_in_match_brute_force() {
+        type Find = for<'a> fn(&'a IntervalTree<Range<i32>>, Range<i32>) -> HashSet<&'a Range<i32>>;
+        type Endpoint = fn(&Range<i32>) -> i32;
+        let cases: [(Find, Endpoint); 2] = [
+            (IntervalTree::find_ending_in, |intv| intv.end),
+            (IntervalTree::find_beginning_in, |intv| intv.start),
+        ];
+
+        let tree = sample_tree();
+        for (find, endpoint) in cases.iter() {
+            for begin in 0..100 {
+                for end in begin..(begin + 10).min(100) {
+                    let expected: HashSet<_> = tree
+                        .iter()
+                        .filter(|intv| (begin..end).contains(&endpoint(intv)))
+                        .collect();
+                    assert_eq!(find(&tree, begin..end), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn capacity_limit_evicts_earliest_ending() {
+        let mut tree = IntervalTree::with_capacity_limit(0..100, 5);
+        let intervals = [
+            40..60,
+            5..10,
+            85..95,
+            20..30,
+            55..70,
+            45..52,
+            1..3,
+            90..100,
+            60..80,
+        ];
+        for intv in intervals.iter() {
+            tree.insert(intv.clone());
+        }
+
+        let mut remaining: Vec<_> = tree.iter().cloned().collect();
+        remaining.sort_by_key(|intv| intv.end);
+        assert_eq!(remaining, vec![40..60, 55..70, 60..80, 85..95, 90..100]);
+        assert_eq!(tree.check_invariants(), Ok(()));
+
+        tree.insert_many_sorted(vec![0..99, 10..98]);
+        let mut remaining: Vec<_> = tree.iter().cloned().collect();
+        remaining.sort_by_key(|intv| intv.end);
+        assert_eq!(remaining, vec![60..80, 85..95, 10..98, 0..99, 90..100]);
+        assert_eq!(tree.check_invariants(), Ok(()));
+    }
+
+    #[test]
+    fn from_tuples() {
+        let tuples = [(0, 5), (3, 8), (85, 95)];
+        let tree = IntervalTree::<Range<i32>>::from_tuples(0..100, &tuples);
+
+        let mut expected = IntervalTree::new(0..100);
+        for &(begin, end) in tuples.iter() {
+            expected.insert(begin..end);
+        }
+
+        for p in 0..100 {
+            assert_eq!(tree.find_with_point(p), expected.find_with_point(p));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn panic_from_tuples_reversed() {
+        IntervalTree::<Range<u8>>::from_tuples(0..100, &[(8, 3)]);
+    }
+
+    fn compact_and_default_trees() -> (IntervalTree<Range<i32>>, IntervalTree<Range<i32>>) {
+        let intervals = [
+            0..5,
+            3..8,
+            10..60,
+            40..60,
+            55..70,
+            60..80,
+            85..95,
+            90..100,
+            49..51,
+        ];
+
+        let mut compact = IntervalTree::new_compact(0..100);
+        let mut default = IntervalTree::new(0..100);
+        for intv in intervals.iter() {
+            compact.insert(intv.clone());
+            default.insert(intv.clone());
+        }
+
+        (compact, default)
+    }
+
+    #[test]
+    fn compact_queries_match_default() {
+        let (compact, default) = compact_and_default_trees();
+        assert_eq!(compact.check_invariants(), Ok(()));
+
+        for p in 0..100 {
+            assert_eq!(compact.find_with_point(p), default.find_with_point(p));
+            assert_eq!(compact.overlaps_any_point(p), default.overlaps_any_point(p));
+            assert_eq!(compact.uniquely_covered(p), default.uniquely_covered(p));
+        }
+        assert_eq!(
+            compact.find_ending_in(50..90),
+            default.find_ending_in(50..90)
+        );
+        assert_eq!(
+            compact.find_with_interval(45..65),
+            default.find_with_interval(45..65)
+        );
+    }
+
+    #[test]
+    fn nodes_omit_tree_settings() {
+        assert_eq!(
+            mem::size_of::<Node<Range<u64>>>(),
+            mem::size_of::<Range<u64>>()
+                + mem::size_of::<u64>()
+                + 2 * mem::size_of::<Option<Box<Node<Range<u64>>>>>()
+                + mem::size_of::<BinaryHeap<BeginSorted<Range<u64>>>>()
+                + mem::size_of::<BinaryHeap<EndSorted<Range<u64>>>>()
+        );
+    }
+
+    #[test]
+    fn len_follows_insertions_and_removals() {
+        let mut tree = sample_tree();
+        assert_eq!(tree.len, tree.iter().count());
+
+        tree.insert_many_sorted(vec![5..10, 45..55]);
+        assert_eq!(tree.len, tree.iter().count());
+
+        tree.remove_with_point(50);
+        assert_eq!(tree.len, tree.iter().count());
+        tree.remove_with_interval(20..30);
+        assert_eq!(tree.len, tree.iter().count());
+        tree.drain_before(40);
+        assert_eq!(tree.len, tree.iter().count());
+
+        let mut limited = IntervalTree::with_capacity_limit(0..100, 3);
+        for intv in sample_intervals() {
+            limited.insert(intv);
+            assert_eq!(limited.len, limited.iter().count());
+        }
+        assert_eq!(limited.len, 3);
+    }
+
+    #[test]
+    fn compact_capacity_limit() {
+        let mut tree = IntervalTree::new_compact(0..100);
+        tree.capacity_limit = Some(2);
+        tree.insert(40..60);
+        tree.insert(45..55);
+        tree.insert(10..90); // evicts 45..55
+
+        let mut remaining: Vec<_> = tree.iter().cloned().collect();
+        remaining.sort_by_key(|intv| intv.end);
+        assert_eq!(remaining, vec![40..60, 10..90]);
+        assert_eq!(tree.check_invariants(), Ok(()));
+
+        tree.rebuild_with_range(0..200);
+        tree.insert(150..160); // evicts 40..60
+        assert!(tree.compact);
+        assert_eq!(tree.check_invariants(), Ok(()));
+    }
+
+    #[test]
+    fn compact_uses_less_memory() {
+        let (compact, default) = compact_and_default_trees();
+        let heap_bytes = |tree: &IntervalTree<Range<i32>>| -> usize {
+            tree.nodes()
+                .map(|node| {
+                    node.overlaps_begin.capacity() * mem::size_of::<BeginSorted<Range<i32>>>()
+                        + node.overlaps_end.capacity() * mem::size_of::<EndSorted<Range<i32>>>()
+                })
+                .sum()
+        };
+
+        assert!(heap_bytes(&compact) < heap_bytes(&default));
+        assert!(compact
+            .nodes()
+            .all(|node| node.overlaps_end.capacity() == 0));
+    }
+
+    #[test]
+    fn options_allow_empty() {
+        let mut tree = IntervalTree::new(0..100);
+        for p in [0, 10, 49, 50, 51, 99].iter() {
+            tree.insert(*p..*p);
+        }
+        tree.insert(40..60);
+        tree.insert_many_sorted(vec![20..20, 20..30, 75..75]);
+
+        assert_eq!(tree.iter().count(), 10);
+        assert_eq!(tree.check_invariants(), Ok(()));
+        for p in 0..100 {
+            assert!(tree
+                .find_with_point_iter(p)
+                .all(|intv| intv.start < intv.end));
+        }
+    }
+
+    #[test]
+    fn options_reject_empty() {
+        let options = Options {
+            allow_empty: false,
+            allow_duplicates: true,
+        };
+        let mut tree = IntervalTree::with_options(0..100, options);
+        tree.insert(50..50);
+        tree.insert(40..60);
+        tree.insert(40..60);
+        tree.insert_many_sorted(vec![20..20, 20..30, 75..75]);
+
+        let mut all: Vec<_> = tree.iter().cloned().collect();
+        all.sort_by_key(|intv| intv.start);
+        assert_eq!(all, vec![20..30, 40..60, 40..60]);
+    }
+
+    #[test]
+    fn options_reject_duplicates() {
+        let options = Options {
+            allow_empty: true,
+            allow_duplicates: false,
+        };
+        let mut tree = IntervalTree::with_options(0..100, options);
+        tree.insert(50..50);
+        tree.insert(50..50);
+        tree.insert(40..60);
+        tree.insert(40..60);
+        tree.insert_many_sorted(vec![20..30, 20..25, 20..30, 40..60]);
+
+        let mut all: Vec<_> = tree.iter().cloned().collect();
+        all.sort_by_key(|intv| (intv.start, intv.end));
+        assert_eq!(all, vec![20..25, 20..30, 40..60, 50..50]);
+
+        tree.rebuild_with_range(0..200);
+        tree.insert(20..30);
+        assert_eq!(tree.iter().count(), 4);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panic_insert_empty_at_end() {
+        IntervalTree::new(0..100).insert(100..100);
+    }
+
+    #[test]
+    fn merge_within() {
+        let mut tree = IntervalTree::new(0..10);
+        tree.insert(0..3);
+        tree.insert(5..8);
+
+        assert_eq!(tree.merge_within(1), vec![0..3, 5..8]);
+        assert_eq!(tree.merge_within(2), vec![0..8]);
+    }
+
+    #[test]
+    fn to_coverage_bitmap() {
+        let mut tree = IntervalTree::new(10..110);
+        for intv in sample_intervals() {
+            if intv.start >= 10 && intv.start % 9 < 3 {
+                tree.insert(intv);
+            }
+        }
+
+        let bitmap = tree.to_coverage_bitmap();
+        assert_eq!(bitmap.len(), 100);
+        for (i, p) in (10..110).enumerate() {
+            assert_eq!(bitmap[i], !tree.find_with_point(p).is_empty());
+        }
+    }
+
+    #[test]
+    fn find_with_point_positions() {
+        let mut tree = IntervalTree::new(0..100);
+        tree.insert(40..70);
+        tree.insert(10..46);
+
+        let mut found = tree.find_with_point_positions(44);
+        found.sort_by_key(|(intv, _)| intv.start);
+        assert_eq!(
+            found,
+            vec![(&(10..46), Position::End), (&(40..70), Position::Start)]
+        );
+
+        let found = tree.find_with_point_positions(50);
+        assert_eq!(found, vec![(&(40..70), Position::Middle)]);
+    }
+
+    #[test]
+    fn find_with_point_positions_thirds() {
+        for len in 1..12 {
+            let mut tree = IntervalTree::new(0..20);
+            tree.insert(0..len);
+            for p in 0..len {
+                let expected = if p * 3 < len {
+                    Position::Start
+                } else if p * 3 < len * 2 {
+                    Position::Middle
+                } else {
+                    Position::End
+                };
+                assert_eq!(
+                    tree.find_with_point_positions(p),
+                    vec![(&(0..len), expected)]
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn find_with_point_positions_wide() {
+        let mut tree = IntervalTree::new(0..u128::MAX);
+        tree.insert(0..u128::MAX - 1);
+        let positions = [(0, Position::Start), (u128::MAX / 2, Position::Middle)];
+        for &(point, position) in positions.iter() {
+            assert_eq!(
+                tree.find_with_point_positions(point),
+                vec![(&(0..u128::MAX - 1), position)]
+            );
+        }
+        assert_eq!(
+            tree.find_with_point_positions(u128::MAX - 2)[0].1,
+            Position::End
+        );
+
+        let mut tree = IntervalTree::new(0..i128::MAX);
+        tree.insert(0..i128::MAX - 1);
+        assert_eq!(
+            tree.find_with_point_positions(i128::MAX - 2)[0].1,
+            Position::End
+        );
+    }
+
+    #[test]
+    fn all_within() {
+        let mut tree = IntervalTree::new(0..100);
+        assert!(tree.all_within(&(40..60)));
+
+        tree.insert(40..60);
+        tree.insert(45..50);
+        tree.insert(55..60);
+        assert!(tree.all_within(&(40..60)));
+
+        tree.insert(39..41);
+        assert!(!tree.all_within(&(40..60)));
+    }
+
+    #[test]
+    fn latest_ending_at() {
+        let mut tree = IntervalTree::new(0..100);
+        tree.insert(10..40);
+        tree.insert(35..90);
+        tree.insert(20..90);
+        tree.insert(30..60);
+        tree.insert(95..99);
+
+        assert_eq!(tree.latest_ending_at(37), Some(&(20..90)));
+        assert_eq!(tree.latest_ending_at(15), Some(&(10..40)));
+        assert_eq!(tree.latest_ending_at(92), None);
+    }
+
+    #[test]
+    fn earliest_starting_at() {
+        let mut tree = IntervalTree::new(0..100);
+        tree.insert(35..90);
+        tree.insert(10..40);
+        tree.insert(10..45);
+        tree.insert(30..60);
+        tree.insert(95..99);
+
+        assert_eq!(tree.earliest_starting_at(37), Some(&(10..45)));
+        assert_eq!(tree.earliest_starting_at(50), Some(&(30..60)));
+        assert_eq!(tree.earliest_starting_at(92), None);
     }
 
     #[test]
-    #[should_panic]
-    fn panic_find_with_point_begin() {
-        let tree = IntervalTree::new(1..11);
-        tree.find_with_point(0);
+    fn char_ranges() {
+        let mut tree = IntervalTree::new('\0'..char::MAX);
+        tree.insert('a'..'m');
+        tree.insert('A'..'M');
+        tree.insert('\u{D7F0}'..'\u{E010}');
+        tree.insert('\u{E000}'..'\u{F000}');
+        tree.insert('\u{D7FF}'..'\u{E000}');
+        assert_eq!(tree.check_invariants(), Ok(()));
+
+        assert_eq!(
+            tree.find_with_point('c'),
+            [&('a'..'m')].iter().cloned().collect()
+        );
+        assert_eq!(
+            tree.find_with_point('\u{D7FF}'),
+            [&('\u{D7F0}'..'\u{E010}'), &('\u{D7FF}'..'\u{E000}')]
+                .iter()
+                .cloned()
+                .collect()
+        );
+        assert_eq!(
+            tree.find_with_point('\u{E000}'),
+            [&('\u{D7F0}'..'\u{E010}'), &('\u{E000}'..'\u{F000}')]
+                .iter()
+                .cloned()
+                .collect()
+        );
+        assert!(tree.find_with_point('\u{F000}').is_empty());
     }
 
     #[test]
-    #[should_panic]
-    fn panic_find_with_point_end() {
-        let tree = IntervalTree::new(0..10);
-        tree.find_with_point(10);
+    fn jaccard() {
+        let mut tree = IntervalTree::new(0..100);
+        tree.insert(0..10);
+        tree.insert(5..20);
+        tree.insert(60..70);
+
+        let mut other = IntervalTree::new(0..100);
+        other.insert(10..30);
+        other.insert(65..75);
+
+        // Both cover 10..20 and 65..70, either covers 0..30 and 60..75.
+        assert_eq!(tree.jaccard(&other), 15.0 / 45.0);
+        assert_eq!(other.jaccard(&tree), tree.jaccard(&other));
+        assert_eq!(tree.jaccard(&tree), 1.0);
+        assert_eq!(tree.jaccard(&IntervalTree::new(0..100)), 0.0);
     }
 
     #[test]
-    #[should_panic]
-    fn panic_find_with_interval_start() {
-        let tree = IntervalTree::new(1..11);
-        tree.find_with_interval(0..10);
+    fn minimal_cover() {
+        let mut tree = IntervalTree::new(0..100);
+        for intv in [10..20, 12..30, 15..40, 25..35, 30..50, 45..60, 70..80].iter() {
+            tree.insert(intv.clone());
+        }
+
+        assert_eq!(
+            tree.minimal_cover(10..50),
+            Some(vec![&(10..20), &(15..40), &(30..50)])
+        );
+        assert_eq!(tree.minimal_cover(20..45), Some(vec![&(15..40), &(30..50)]));
+        assert_eq!(tree.minimal_cover(72..75), Some(vec![&(70..80)]));
+        assert_eq!(tree.minimal_cover(40..40), Some(vec![]));
+        assert_eq!(tree.minimal_cover(5..15), None);
+        assert_eq!(tree.minimal_cover(50..75), None);
     }
 
     #[test]
-    #[should_panic]
-    fn panic_find_with_interval_end() {
-        let tree = IntervalTree::new(0..10);
-        tree.find_with_interval(1..11);
+    fn split_at_points() {
+        let mut tree = IntervalTree::new(0..100);
+        tree.insert(0..10);
+        assert_eq!(tree.split_at_points(&[3, 7]), vec![0..3, 3..7, 7..10]);
+        assert_eq!(tree.split_at_points(&[0, 10, 50]), vec![0..10]);
+        assert_eq!(tree.split_at_points(&[]), vec![0..10]);
+    }
+
+    #[test]
+    fn count_of() {
+        let mut tree = IntervalTree::new(0..100);
+        for _ in 0..3 {
+            tree.insert(10..20);
+            tree.insert(45..55);
+        }
+        tree.insert(10..21);
+        tree.insert(50..50);
+
+        assert_eq!(tree.count_of(&(10..20)), 3);
+        assert_eq!(tree.count_of(&(45..55)), 3);
+        assert_eq!(tree.count_of(&(10..21)), 1);
+        assert_eq!(tree.count_of(&(50..50)), 1);
+        assert_eq!(tree.count_of(&(80..90)), 0);
+    }
+
+    #[test]
+    fn gaps_iter() {
+        let mut tree = IntervalTree::new(0..100);
+        assert_eq!(tree.gaps_iter().collect::<Vec<_>>(), tree.gaps());
+
+        for i in 0..90 {
+            if i % 11 < 4 {
+                tree.insert(i..(i + (i % 5) + 1));
+            }
+        }
+        tree.insert(8..8);
+        tree.insert(95..100);
+
+        assert_eq!(tree.gaps_iter().next(), Some(tree.gaps()[0]));
+        assert_eq!(tree.gaps_iter().collect::<Vec<_>>(), tree.gaps());
+
+        tree.insert(0..100);
+        assert_eq!(tree.gaps_iter().next(), None);
+    }
+
+    #[test]
+    fn overlap_adjacency() {
+        let mut tree = IntervalTree::new(0..100);
+        for intv in [0..10, 5..15, 9..12, 10..20, 30..40, 45..55].iter() {
+            tree.insert(intv.clone());
+        }
+
+        let adjacency = tree.overlap_adjacency();
+        assert_eq!(adjacency.len(), 6);
+        assert_eq!(
+            adjacency[&(5..15)],
+            [&(0..10), &(9..12), &(10..20)].iter().cloned().collect()
+        );
+        assert_eq!(
+            adjacency[&(10..20)],
+            [&(5..15), &(9..12)].iter().cloned().collect()
+        );
+        assert!(adjacency[&(30..40)].is_empty());
+    }
+
+    #[test]
+    fn covers_same_as() {
+        let mut tree = IntervalTree::new(0..100);
+        for intv in [0..10, 5..15, 9..12, 30..40, 35..38].iter() {
+            tree.insert(intv.clone());
+        }
+
+        let mut other = IntervalTree::new(0..200);
+        for intv in [0..3, 3..15, 30..33, 32..40, 40..40].iter() {
+            other.insert(intv.clone());
+        }
+        assert!(tree.covers_same_as(&other));
+        assert!(other.covers_same_as(&tree));
+
+        other.insert(15..16);
+        assert!(!tree.covers_same_as(&other));
+        assert!(!tree.covers_same_as(&IntervalTree::new(0..100)));
+    }
+
+    #[test]
+    fn from_intervals_padded() {
+        let intervals = vec![40..50, 20..30, 45..60];
+        let mut tree =
+            IntervalTree::<Range<i32>>::from_intervals_padded(intervals.clone(), 10).unwrap();
+        assert_eq!(tree.root.range, 10..70);
+
+        tree.insert(15..20);
+        tree.insert(60..70);
+        for p in 10..70 {
+            let expected = intervals
+                .iter()
+                .chain([15..20, 60..70].iter())
+                .filter(|intv| intv.contains(&p))
+                .collect();
+            assert_eq!(tree.find_with_point(p), expected);
+        }
+
+        assert!(IntervalTree::<Range<i32>>::from_intervals_padded(vec![], 10).is_none());
+    }
+
+    #[test]
+    fn from_intervals_padded_saturates() {
+        let tree =
+            IntervalTree::<Range<u8>>::from_intervals_padded(vec![3..10, 100..120], 10).unwrap();
+        assert_eq!(tree.root.range, 0..130);
+        assert_eq!(tree.iter().count(), 2);
+    }
+
+    #[test]
+    fn from_intervals_padded_empty_intervals() {
+        let tree =
+            IntervalTree::<Range<i32>>::from_intervals_padded(vec![0..10, 10..10], 0).unwrap();
+        assert_eq!(tree.root.range, 0..11);
+        assert_eq!(tree.count_of(&(10..10)), 1);
+
+        let tree = IntervalTree::<Range<u8>>::from_intervals_padded(Some(5..5), 2).unwrap();
+        assert_eq!(tree.root.range, 3..8);
+        assert_eq!(tree.count_of(&(5..5)), 1);
+    }
+
+    #[test]
+    fn covered_span_touching() {
+        let mut tree = IntervalTree::new(0..100);
+        for intv in [30..40, 35..50, 45..60, 55..58, 60..65, 80..90].iter() {
+            tree.insert(intv.clone());
+        }
+
+        assert_eq!(tree.covered_span_touching(46..47), Some(30..65));
+        assert_eq!(tree.covered_span_touching(64..85), Some(30..90));
+        assert_eq!(tree.covered_span_touching(65..80), None);
+        assert_eq!(tree.covered_span_touching(40..40), None);
+    }
+
+    #[test]
+    fn component_count() {
+        let mut tree = IntervalTree::new(0..100);
+        for intv in [0..5, 3..8, 8..10, 20..25, 21..22, 40..60, 45..50, 70..70].iter() {
+            tree.insert(intv.clone());
+        }
+
+        assert_eq!(tree.component_count(), 3);
+        assert_eq!(tree.component_count(), tree.regions_over_threshold(1).len());
+    }
+
+    #[test]
+    fn find_near_point() {
+        let mut tree = IntervalTree::new(0u32..100);
+        tree.insert(0..5);
+        tree.insert(10..20);
+        tree.insert(90..100);
+
+        assert!(tree.find_near_point(7, 0).is_empty());
+        assert_eq!(
+            tree.find_near_point(7, 3),
+            [&(0..5), &(10..20)].iter().cloned().collect()
+        );
+        assert_eq!(
+            tree.find_near_point(2, 50),
+            [&(0..5), &(10..20)].iter().cloned().collect()
+        );
+        assert_eq!(
+            tree.find_near_point(95, 10),
+            [&(90..100)].iter().cloned().collect()
+        );
+        assert_eq!(tree.find_near_point(50, 80).len(), 3);
+    }
+
+    #[test]
+    fn is_contiguous_over() {
+        let mut tree = IntervalTree::new(0..100);
+        tree.insert(10..60);
+        assert!(tree.is_contiguous_over(20..30));
+        assert!(tree.is_contiguous_over(0..100));
+
+        let mut tree = IntervalTree::new(0..100);
+        tree.insert(10..35);
+        tree.insert(30..60);
+        assert!(tree.is_contiguous_over(10..60));
+        assert!(tree.is_contiguous_over(5..65));
+
+        let mut tree = IntervalTree::new(0..100);
+        tree.insert(10..30);
+        tree.insert(40..60);
+        assert!(!tree.is_contiguous_over(10..60));
+        assert!(tree.is_contiguous_over(35..60));
+        assert!(!tree.is_contiguous_over(30..40));
+    }
+
+    #[test]
+    fn render_ascii() {
+        let mut tree = IntervalTree::new(0..100);
+        tree.insert(50..100);
+        tree.insert(0..26);
+        tree.insert(44..46);
+        tree.insert(0..1);
+
+        let expected = "\
+|---------|
+*
+*--o
+    *o
+     *----o
+";
+        assert_eq!(tree.render_ascii(10), expected);
+        assert_eq!(IntervalTree::new(0..100).render_ascii(0), "|\n");
+    }
+
+    #[test]
+    fn find_by_length() {
+        let mut tree = IntervalTree::new(0..100);
+        for intv in [0..1, 3..6, 10..14, 20..27, 30..38, 40..60, 50..50].iter() {
+            tree.insert(intv.clone());
+        }
+
+        assert_eq!(
+            tree.find_by_length(3, 7),
+            [&(3..6), &(10..14), &(20..27)].iter().cloned().collect()
+        );
+        assert_eq!(
+            tree.find_by_length(0, 0),
+            [&(50..50)].iter().cloned().collect()
+        );
+        assert!(tree.find_by_length(21, 99).is_empty());
+    }
+
+    #[test]
+    fn depth_events() {
+        let mut tree = IntervalTree::new(0..100);
+        assert_eq!(tree.depth_events(), vec![]);
+
+        for intv in [40..60, 45..55, 45..50, 55..70, 60..70, 80..80].iter() {
+            tree.insert(intv.clone());
+        }
+        assert_eq!(
+            tree.depth_events(),
+            vec![(40, 1), (45, 2), (50, -1), (70, -2)]
+        );
+    }
+
+    #[test]
+    fn find_with_point_by_insertion() {
+        let order = [50..60, 10..90, 55..56, 0..100, 52..70, 30..58, 10..90];
+
+        let mut tree = IntervalTree::new(0..100);
+        for intv in order.iter() {
+            tree.insert(intv.clone());
+        }
+        let expected: Vec<_> = order.iter().filter(|intv| intv.contains(&55)).collect();
+        assert_eq!(tree.find_with_point_by_insertion(55), expected);
+
+        tree.rebuild_with_range(0..200);
+        tree.insert(54..150);
+        let mut expected = expected;
+        expected.push(&(54..150));
+        assert_eq!(tree.find_with_point_by_insertion(55), expected);
+
+        let mut tree = IntervalTree::new_compact(0..100);
+        tree.insert(50..60);
+        tree.insert_many_sorted(vec![30..58, 52..70]);
+        tree.insert(10..90);
+        assert_eq!(
+            tree.find_with_point_by_insertion(55),
+            vec![&(50..60), &(30..58), &(52..70), &(10..90)]
+        );
+    }
+
+    #[test]
+    fn depth_stats() {
+        let mut tree = IntervalTree::new(0..100);
+        tree.insert(10..30);
+        tree.insert(20..40);
+        tree.insert(25..35);
+        tree.insert(90..100);
+
+        // Depth 1 over 10..20, 2 over 20..25, 3 over 25..30, 2 over 30..35, 1 over 35..40 and
+        // 90..100.
+        let stats = tree.depth_stats();
+        assert_eq!(stats.min, 0);
+        assert_eq!(stats.max, 3);
+        assert_eq!(stats.mean, 0.6);
+        assert_eq!(stats.mean, tree.average_depth());
+
+        tree.insert(0..100);
+        assert_eq!(tree.depth_stats().min, 1);
+        assert_eq!(tree.depth_stats().max, 4);
+    }
+
+    #[test]
+    fn remove_with_point() {
+        let intervals = [0..10, 5..15, 12..14, 40..60, 45..55, 20..90, 70..80, 95..99];
+
+        for &compact in [false, true].iter() {
+            let mut tree = if compact {
+                IntervalTree::new_compact(0..100)
+            } else {
+                IntervalTree::new(0..100)
+            };
+            for intv in intervals.iter() {
+                tree.insert(intv.clone());
+            }
+
+            let mut removed = tree.remove_with_point(50);
+            removed.sort_by_key(|intv| intv.start);
+            assert_eq!(removed, vec![20..90, 40..60, 45..55]);
+            assert_eq!(tree.check_invariants(), Ok(()));
+
+            assert!(tree.remove_with_point(50).is_empty());
+            let mut remaining: Vec<_> = tree.iter().cloned().collect();
+            remaining.sort_by_key(|intv| intv.start);
+            assert_eq!(remaining, vec![0..10, 5..15, 12..14, 70..80, 95..99]);
+        }
+    }
+
+    #[test]
+    fn remove_with_interval() {
+        let intervals = [0..10, 5..15, 12..14, 40..60, 45..55, 20..90, 70..80, 95..99];
+
+        let mut tree = IntervalTree::new(0..100);
+        for intv in intervals.iter() {
+            tree.insert(intv.clone());
+        }
+
+        let mut removed = tree.remove_with_interval(14..75);
+        removed.sort_by_key(|intv| intv.start);
+        assert_eq!(removed, vec![5..15, 20..90, 40..60, 45..55, 70..80]);
+        assert_eq!(tree.check_invariants(), Ok(()));
+
+        let mut remaining: Vec<_> = tree.iter().cloned().collect();
+        remaining.sort_by_key(|intv| intv.start);
+        assert_eq!(remaining, vec![0..10, 12..14, 95..99]);
+        assert!(tree.remove_with_interval(50..50).is_empty());
+    }
+
+    #[test]
+    fn estimated_memory() {
+        let mut tree = IntervalTree::new(0..1000);
+        let mut estimates = vec![tree.estimated_memory()];
+        for n in 1..=4 {
+            for i in 0..(n * 100) {
+                tree.insert(i..(i + 10));
+            }
+            estimates.push(tree.estimated_memory());
+        }
+
+        assert!(estimates.windows(2).all(|w| w[0] < w[1]));
+        // Storing each interval costs at least one copy in each heap.
+        let intervals = tree.iter().count();
+        assert!(
+            tree.estimated_memory()
+                >= intervals
+                    * (mem::size_of::<BeginSorted<Range<i32>>>()
+                        + mem::size_of::<EndSorted<Range<i32>>>())
+        );
+
+        let mut compact = IntervalTree::new_compact(0..1000);
+        for intv in tree.iter() {
+            compact.insert(intv.clone());
+        }
+        assert!(compact.estimated_memory() < tree.estimated_memory());
+    }
+
+    #[test]
+    fn find_with_point_depth_band() {
+        let mut tree = IntervalTree::new(0..100);
+        tree.insert(45..55);
+        tree.insert(20..60);
+        tree.insert(40..70);
+        tree.insert(20..90);
+        tree.insert(80..90);
+
+        assert_eq!(
+            tree.find_with_point_depth_band(50, 1, 2),
+            vec![&(20..60), &(40..70)]
+        );
+        assert_eq!(tree.find_with_point_depth_band(50, 3, 10), vec![&(45..55)]);
+        assert_eq!(tree.find_with_point_depth_band(50, 0, 0), vec![&(20..90)]);
+        assert!(tree.find_with_point_depth_band(50, 4, 10).is_empty());
+        assert!(tree.find_with_point_depth_band(50, 2, 1).is_empty());
+    }
+
+    #[test]
+    fn shadowed() {
+        let mut tree = IntervalTree::new(0..100);
+        for intv in [
+            10..50,
+            10..50,
+            10..30,
+            20..50,
+            25..35,
+            45..70,
+            60..70,
+            80..90,
+            80..90,
+        ]
+        .iter()
+        {
+            tree.insert(intv.clone());
+        }
+
+        assert_eq!(
+            tree.shadowed(),
+            vec![&(10..30), &(20..50), &(25..35), &(60..70)]
+        );
+    }
+
+    #[test]
+    fn union() {
+        let mut tree = IntervalTree::new(0..100);
+        for intv in [0..10, 5..15, 40..60, 50..60].iter() {
+            tree.insert(intv.clone());
+        }
+        let mut other = IntervalTree::new(50..200);
+        for intv in [50..60, 55..150, 150..199].iter() {
+            other.insert(intv.clone());
+        }
+
+        let union = tree.union(&other);
+        assert_eq!(union.root.range, 0..200);
+        assert_eq!(union.check_invariants(), Ok(()));
+        assert_eq!(union.count_of(&(50..60)), 2);
+        for p in 0..200 {
+            let mut expected: Vec<_> = tree
+                .iter()
+                .chain(other.iter())
+                .filter(|intv| intv.contains(&p))
+                .collect();
+            let mut found: Vec<_> = union.find_with_point_iter(p).collect();
+            expected.sort_by_key(|intv| (intv.start, intv.end));
+            found.sort_by_key(|intv| (intv.start, intv.end));
+            assert_eq!(found, expected);
+        }
+    }
+
+    #[test]
+    fn intersection() {
+        let mut tree = IntervalTree::new(0..100);
+        for intv in [0..10, 5..15, 40..60, 40..60, 40..60, 50..60, 90..99].iter() {
+            tree.insert(intv.clone());
+        }
+        let mut other = IntervalTree::new(0..200);
+        for intv in [5..15, 40..60, 40..60, 50..61, 90..99, 150..199].iter() {
+            other.insert(intv.clone());
+        }
+
+        let intersection = tree.intersection(&other);
+        assert_eq!(intersection.root.range, 0..100);
+        assert_eq!(intersection.check_invariants(), Ok(()));
+
+        let mut shared: Vec<_> = intersection.iter().cloned().collect();
+        shared.sort_by_key(|intv| intv.start);
+        assert_eq!(shared, vec![5..15, 40..60, 40..60, 90..99]);
+        assert_eq!(
+            other.intersection(&tree).iter().count(),
+            intersection.iter().count()
+        );
+    }
+
+    #[test]
+    fn assign_lanes() {
+        let mut tree = IntervalTree::new(0..100);
+        for intv in [10..40, 20..50, 30..60, 70..80, 85..95].iter() {
+            tree.insert(intv.clone());
+        }
+
+        let lanes = tree.assign_lanes();
+        assert_eq!(
+            lanes,
+            vec![
+                vec![&(10..40), &(70..80)],
+                vec![&(20..50), &(85..95)],
+                vec![&(30..60)],
+            ]
+        );
+        for lane in lanes.iter() {
+            assert!(lane.windows(2).all(|w| !w[0].overlaps(w[1])));
+        }
+    }
+
+    #[test]
+    fn coverage_subset_of() {
+        let mut tree = IntervalTree::new(0..100);
+        tree.insert(2..5);
+        tree.insert(20..30);
+
+        let mut other = IntervalTree::new(0..100);
+        other.insert(0..10);
+        other.insert(20..25);
+        other.insert(25..30);
+        assert!(tree.coverage_subset_of(&other));
+        assert!(IntervalTree::new(0..100).coverage_subset_of(&tree));
+
+        tree.insert(29..31);
+        assert!(!tree.coverage_subset_of(&other));
+    }
+
+    #[test]
+    fn next_uncovered() {
+        let mut tree = IntervalTree::new(0..100);
+        tree.insert(0..10);
+        tree.insert(10..20);
+        tree.insert(25..30);
+        tree.insert(40..100);
+
+        assert_eq!(tree.next_uncovered(0), Some(20));
+        assert_eq!(tree.next_uncovered(15), Some(20));
+        assert_eq!(tree.next_uncovered(22), Some(22));
+        assert_eq!(tree.next_uncovered(25), Some(30));
+        assert_eq!(tree.next_uncovered(40), None);
+    }
+
+    #[test]
+    fn next_covered() {
+        let mut tree = IntervalTree::new(0..100);
+        assert_eq!(tree.next_covered(0), None);
+
+        tree.insert(0..10);
+        tree.insert(25..30);
+        tree.insert(28..40);
+
+        assert_eq!(tree.next_covered(0), Some(0));
+        assert_eq!(tree.next_covered(10), Some(25));
+        assert_eq!(tree.next_covered(17), Some(25));
+        assert_eq!(tree.next_covered(35), Some(35));
+        assert_eq!(tree.next_covered(40), None);
+    }
+
+    #[test]
+    fn filter() {
+        let mut tree = IntervalTree::new(0..100);
+        tree.insert(0..2);
+        tree.insert(5..15);
+        tree.insert(20..23);
+        tree.insert(30..60);
+        tree.insert(40..44);
+        tree.insert(90..99);
+
+        let filtered = tree.filter(0..80, |intv| intv.end - intv.start > 3);
+
+        assert_eq!(filtered.check_invariants(), Ok(()));
+        assert_eq!(
+            filtered.find_with_interval(0..80),
+            [&(5..15), &(30..60), &(40..44)].iter().cloned().collect()
+        );
+        assert_eq!(
+            filtered.find_with_point(42),
+            [&(30..60), &(40..44)].iter().cloned().collect()
+        );
+        assert!(filtered.find_with_point(21).is_empty());
+        assert_eq!(tree.find_with_interval(0..100).len(), 6);
+    }
+
+    #[test]
+    fn gap_at() {
+        let mut tree = IntervalTree::new(0..100);
+        assert_eq!(tree.gap_at(42), Some((0, 100)));
+
+        tree.insert(10..20);
+        tree.insert(15..30);
+        tree.insert(40..40);
+        tree.insert(60..70);
+        tree.insert(70..100);
+
+        assert_eq!(tree.gap_at(0), Some((0, 10)));
+        assert_eq!(tree.gap_at(35), Some((30, 60)));
+        assert_eq!(tree.gap_at(40), Some((30, 60)));
+        assert_eq!(tree.gap_at(10), None);
+        assert_eq!(tree.gap_at(25), None);
+        assert_eq!(tree.gap_at(70), None);
+        assert_eq!(tree.gap_at(99), None);
+    }
+
+    #[test]
+    fn find_with_point_by_branch() {
+        let mut tree = IntervalTree::new(0..100);
+        tree.insert(45..55);
+        tree.insert(10..30);
+        tree.insert(20..25);
+        tree.insert(60..90);
+        tree.insert(70..80);
+
+        let found = tree.find_with_point_by_branch(22);
+        assert!(found.node.is_empty());
+        assert_eq!(found.left, [&(10..30), &(20..25)].iter().cloned().collect());
+        assert!(found.right.is_empty());
+
+        let found = tree.find_with_point_by_branch(52);
+        assert_eq!(found.node, [&(45..55)].iter().cloned().collect());
+        assert!(found.left.is_empty());
+        assert!(found.right.is_empty());
+
+        let found = tree.find_with_point_by_branch(75);
+        assert!(found.node.is_empty());
+        assert!(found.left.is_empty());
+        assert_eq!(
+            found.right,
+            [&(60..90), &(70..80)].iter().cloned().collect()
+        );
+    }
+
+    #[test]
+    fn most_overlapping() {
+        let mut tree = IntervalTree::new(0..100);
+        tree.insert(0..5);
+        tree.insert(10..15);
+        tree.insert(20..25);
+        tree.insert(30..35);
+        tree.insert(3..33);
+        tree.insert(60..70);
+        tree.insert(65..75);
+
+        assert_eq!(tree.most_overlapping(), Some(&(3..33)));
+
+        let mut tree = IntervalTree::new(0..100);
+        tree.insert(60..70);
+        tree.insert(10..20);
+        tree.insert(65..75);
+        tree.insert(15..25);
+        assert_eq!(tree.most_overlapping(), Some(&(60..70)));
+    }
+
+    #[test]
+    fn maximal_cliques() {
+        let mut tree = IntervalTree::new(0..100);
+        assert!(tree.maximal_cliques().is_empty());
+
+        for intv in [0..10, 5..15, 8..20, 12..30, 25..40, 40..40, 50..60].iter() {
+            tree.insert(intv.clone());
+        }
+
+        assert_eq!(
+            tree.maximal_cliques(),
+            vec![
+                vec![&(0..10), &(5..15), &(8..20)],
+                vec![&(5..15), &(8..20), &(12..30)],
+                vec![&(12..30), &(25..40)],
+                vec![&(50..60)],
+            ]
+        );
+    }
+
+    #[test]
+    fn coverage_rle() {
+        let mut tree = IntervalTree::new(0..100);
+        tree.insert(0..10);
+        tree.insert(5..30);
+        tree.insert(40..40);
+        tree.insert(50..60);
+        tree.insert(90..100);
+
+        assert_eq!(
+            tree.coverage_rle(),
+            vec![(true, 30), (false, 20), (true, 10), (false, 30), (true, 10)]
+        );
+
+        let total: i32 = tree.coverage_rle().iter().map(|(_, len)| len).sum();
+        assert_eq!(total, 100);
+    }
+
+    #[test]
+    fn nearest_disjoint() {
+        let mut tree = IntervalTree::new(0..100);
+        assert_eq!(tree.nearest_disjoint(40..50), None);
+
+        tree.insert(45..48);
+        tree.insert(10..37);
+        tree.insert(55..60);
+        tree.insert(80..90);
+
+        // 45..48 is the nearest, but overlaps the query.
+        assert_eq!(tree.nearest_disjoint(40..50), Some(&(10..37)));
+        assert_eq!(tree.nearest_disjoint(40..53), Some(&(55..60)));
+        assert_eq!(tree.nearest_disjoint(37..50), Some(&(10..37)));
+        assert_eq!(tree.nearest_disjoint(0..100), None);
+    }
+
+    #[test]
+    fn coverage_approx_eq() {
+        let mut tree = IntervalTree::new(0..100);
+        tree.insert(10..30);
+        tree.insert(60..75);
+
+        // Differs only at 29 and 74, between the multiples of 5.
+        let mut other = IntervalTree::new(0..100);
+        other.insert(10..20);
+        other.insert(18..29);
+        other.insert(60..74);
+
+        assert!(tree.coverage_approx_eq(&other, 5));
+        assert!(other.coverage_approx_eq(&tree, 5));
+        assert!(!tree.coverage_approx_eq(&other, 1));
+        assert!(!tree.covers_same_as(&other));
+
+        other.insert(40..45);
+        assert!(!tree.coverage_approx_eq(&other, 5));
+    }
+
+    #[test]
+    fn find_containing_all() {
+        let mut tree = IntervalTree::new(0..100);
+        tree.insert(0..40);
+        tree.insert(10..60);
+        tree.insert(30..35);
+        tree.insert(50..90);
+
+        assert_eq!(
+            tree.find_containing_all(&[12, 32, 55]),
+            [&(10..60)].iter().cloned().collect()
+        );
+        assert_eq!(
+            tree.find_containing_all(&[32]),
+            [&(0..40), &(10..60), &(30..35)].iter().cloned().collect()
+        );
+        assert!(tree.find_containing_all(&[5, 95]).is_empty());
+        assert_eq!(tree.find_containing_all(&[]).len(), 4);
+    }
+
+    #[test]
+    fn find_containing_any() {
+        let mut tree = IntervalTree::new(0..100);
+        tree.insert(0..40);
+        tree.insert(10..60);
+        tree.insert(30..35);
+        tree.insert(50..90);
+        tree.insert(95..99);
+
+        assert_eq!(
+            tree.find_containing_any(&[5, 32, 96]),
+            [&(0..40), &(10..60), &(30..35), &(95..99)]
+                .iter()
+                .cloned()
+                .collect()
+        );
+        assert_eq!(
+            tree.find_containing_any(&[55, 85]),
+            [&(10..60), &(50..90)].iter().cloned().collect()
+        );
+        assert!(tree.find_containing_any(&[92]).is_empty());
+    }
+
+    #[test]
+    fn try_from_str_pairs() {
+        let pairs = vec![("5", "10"), ("40", "60"), ("45", " 55")];
+        let tree = IntervalTree::<Range<i32>>::try_from_str_pairs(0..100, pairs).unwrap();
+        assert_eq!(
+            tree.find_with_point(50),
+            [&(40..60), &(45..55)].iter().cloned().collect()
+        );
+        assert_eq!(
+            tree.find_with_point(7),
+            [&(5..10)].iter().cloned().collect()
+        );
+
+        let pairs = vec![
+            ("5".to_string(), "10".to_string()),
+            ("40".to_string(), "6O".to_string()),
+            ("x".to_string(), "55".to_string()),
+        ];
+        let err = IntervalTree::<Range<i32>>::try_from_str_pairs(0..100, pairs).unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::InvalidEndpoint {
+                line: 1,
+                field: 1,
+                source: "6O".parse::<i32>().unwrap_err(),
+            }
+        );
+        assert!(err.source().is_some());
+        assert!(err
+            .to_string()
+            .starts_with("invalid end of the interval at line 1"));
+
+        let err =
+            IntervalTree::<Range<u8>>::try_from_str_pairs(0..100, vec![("-1", "2")]).unwrap_err();
+        assert_eq!(err.line(), 0);
+        assert!(matches!(err, ParseError::InvalidEndpoint { field: 0, .. }));
+
+        let pairs = vec![("5", "10"), ("10", "5")];
+        let err = IntervalTree::<Range<i32>>::try_from_str_pairs(0..100, pairs).unwrap_err();
+        assert_eq!(err, ParseError::Reversed { line: 1 });
+        assert!(err.source().is_none());
+        assert_eq!(err.to_string(), "reversed interval at line 1");
+
+        let pairs = vec![("10", "500")];
+        let err = IntervalTree::<Range<i32>>::try_from_str_pairs(0..100, pairs).unwrap_err();
+        assert_eq!(err, ParseError::OutOfRange { line: 0 });
+        assert_eq!(err.to_string(), "out-of-range interval at line 0");
+
+        let pairs = vec![("100", "100")];
+        let err = IntervalTree::<Range<i32>>::try_from_str_pairs(0..100, pairs).unwrap_err();
+        assert_eq!(err, ParseError::OutOfRange { line: 0 });
+    }
+
+    #[test]
+    fn iter_sorted_by_end() {
+        let mut tree = IntervalTree::new(0..100);
+        assert_eq!(tree.iter_sorted_by_end().next(), None);
+
+        let intervals: Vec<_> = (0..50).map(|i| (i * 7) % 50..(i * 13) % 50 + 50).collect();
+        for intv in intervals.iter() {
+            tree.insert(intv.clone());
+        }
+
+        let mut sorted: Vec<_> = tree.iter_sorted_by_end().collect();
+        assert_eq!(sorted.len(), intervals.len());
+        assert!(sorted.windows(2).all(|w| w[0].end <= w[1].end));
+
+        let mut expected: Vec<_> = intervals.iter().collect();
+        expected.sort_by_key(|intv| (intv.end, intv.start));
+        sorted.sort_by_key(|intv| (intv.end, intv.start));
+        assert_eq!(sorted, expected);
+    }
+
+    #[test]
+    fn enclosing_span_at() {
+        let mut tree = IntervalTree::new(0..100);
+        tree.insert(40..45);
+        tree.insert(20..42);
+        tree.insert(41..70);
+        tree.insert(30..50);
+        tree.insert(60..90);
+        tree.insert(0..10);
+
+        assert_eq!(tree.enclosing_span_at(41), Some(20..70));
+        assert_eq!(tree.enclosing_span_at(46), Some(30..70));
+        assert_eq!(tree.enclosing_span_at(65), Some(41..90));
+        assert_eq!(tree.enclosing_span_at(5), Some(0..10));
+        assert_eq!(tree.enclosing_span_at(15), None);
+    }
+
+    #[test]
+    fn coverage_approx_eq_near_max() {
+        let mut tree = IntervalTree::new(0u8..255);
+        let mut other = IntervalTree::new(0u8..255);
+        assert!(tree.coverage_approx_eq(&other, 10));
+        assert!(tree.coverage_approx_eq(&other, 254));
+        assert!(tree.coverage_approx_eq(&other, 255));
+
+        tree.insert(20..60);
+        other.insert(20..58);
+        assert!(tree.coverage_approx_eq(&other, 10));
+        assert!(!tree.coverage_approx_eq(&other, 1));
     }
 }
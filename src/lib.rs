@@ -51,8 +51,19 @@
 //! );
 //! ```
 
+mod builder;
+#[cfg(feature = "serde")]
+mod compact;
 mod interval;
 mod interval_tree;
+mod multimap;
 
-pub use crate::interval::Interval;
-pub use crate::interval_tree::IntervalTree;
+pub use crate::builder::{Builder, CheckedAdd};
+#[cfg(feature = "serde")]
+pub use crate::compact::CompactTree;
+pub use crate::interval::{IdInterval, Interval, OpenInterval};
+pub use crate::interval_tree::{
+    BranchResult, DepthStats, IntervalTree, NodeStat, Options, ParseError, PointEvents, Position,
+    WindowCursor,
+};
+pub use crate::multimap::IntervalMultiMap;
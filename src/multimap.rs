@@ -0,0 +1,103 @@
+use std::{collections::HashSet, hash::Hash};
+
+use crate::{
+    interval::{IdInterval, Interval},
+    interval_tree::IntervalTree,
+};
+
+/// Interval tree whose [`Interval`](trait.Interval.html)s each carry a set of tags, so that
+/// queries can be narrowed down to a tag.
+///
+/// # Examples
+///
+/// ```rust
+/// use interval_tree::{Interval, IntervalMultiMap};
+///
+/// let mut map = IntervalMultiMap::new(0..100);
+///
+/// map.insert(10..30, ["meeting", "urgent"].iter().cloned().collect());
+/// map.insert(20..40, ["meeting"].iter().cloned().collect());
+///
+/// assert_eq!(
+///     map.find_with_point_tagged(25, &"urgent"),
+///     [&(10..30)].iter().cloned().collect()
+/// );
+/// assert_eq!(map.find_with_point_tagged(25, &"meeting").len(), 2);
+/// ```
+pub struct IntervalMultiMap<T, Tag>
+where
+    T: Interval,
+    T::Item: Ord,
+{
+    // Intervals are identified by the index of their tags in `tags`.
+    tree: IntervalTree<IdInterval<T, usize>>,
+    tags: Vec<HashSet<Tag>>,
+}
+
+impl<T, Tag> IntervalMultiMap<T, Tag>
+where
+    T: Interval,
+    T::Item: Ord,
+{
+    /// Creates a map on `range`.
+    pub fn new(range: T) -> Self {
+        Self {
+            tree: IntervalTree::new(IdInterval::new(range, 0)),
+            tags: Vec::new(),
+        }
+    }
+
+    /// Inserts an [`Interval`](trait.Interval.html) tagged with `tags`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the interval overflows the range of this map.
+    pub fn insert(&mut self, interval: T, tags: HashSet<Tag>) {
+        self.tree.insert(IdInterval::new(interval, self.tags.len()));
+        self.tags.push(tags);
+    }
+
+    /// Finds [`Interval`](trait.Interval.html)s in this map that contain the `point` and are
+    /// tagged with `tag`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the point is out-of-range of this map.
+    pub fn find_with_point_tagged(&self, point: T::Item, tag: &Tag) -> HashSet<&T>
+    where
+        T: Eq + Hash,
+        Tag: Eq + Hash,
+    {
+        self.tree
+            .find_with_point_iter(point)
+            .filter(|intv| self.tags[intv.id].contains(tag))
+            .map(|intv| &intv.interval)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_with_point_tagged() {
+        let mut map = IntervalMultiMap::new(0..100);
+        map.insert(40..60, ["a", "b"].iter().cloned().collect());
+        map.insert(45..55, ["b"].iter().cloned().collect());
+        map.insert(45..55, ["c"].iter().cloned().collect());
+        map.insert(5..10, ["a"].iter().cloned().collect());
+
+        assert_eq!(
+            map.find_with_point_tagged(50, &"a"),
+            [&(40..60)].iter().cloned().collect()
+        );
+        assert_eq!(
+            map.find_with_point_tagged(50, &"c"),
+            [&(45..55)].iter().cloned().collect()
+        );
+        assert_eq!(map.find_with_point_tagged(50, &"b").len(), 2);
+        assert!(map.find_with_point_tagged(50, &"d").is_empty());
+        assert!(map.find_with_point_tagged(7, &"b").is_empty());
+    }
+}